@@ -1,5 +1,6 @@
-use datafake_rs::{DataFakeError, DataGenerator};
-use serde_json::json;
+use datafake_rs::engine::Engine;
+use datafake_rs::{DataFakeError, DataGenerator, GenerationContext};
+use serde_json::{Map, Value, json};
 use std::time::Instant;
 
 fn main() -> Result<(), DataFakeError> {
@@ -365,7 +366,7 @@ fn main() -> Result<(), DataFakeError> {
         }
     });
 
-    let generator = DataGenerator::from_value(config)?;
+    let generator = DataGenerator::from_value(config.clone())?;
 
     println!("Starting benchmark: generating complex data 100,000 times...");
     println!("This benchmark uses a mix of:");
@@ -404,6 +405,42 @@ fn main() -> Result<(), DataFakeError> {
         iterations as f64 / duration.as_secs_f64()
     );
 
+    // Compare against the pre-plan evaluation path, which walked the raw schema
+    // and compiled every JSONLogic leaf again for each record
+    let baseline_iterations = 10_000;
+    let start = Instant::now();
+    for _ in 0..baseline_iterations {
+        let mut context = GenerationContext::new();
+        for (name, expression) in &generator.config().variables {
+            let value = evaluate_per_leaf(expression, &context)?;
+            context.set_variable(name.clone(), value);
+        }
+        let _ = evaluate_per_leaf(&generator.config().schema, &context)?;
+    }
+    let baseline = start.elapsed() / baseline_iterations as u32;
+    let compiled = duration / iterations as u32;
+
+    println!("\n=== Compiled Plan vs Per-Call Compilation ===");
+    println!("Per-call compilation: {baseline:.2?} per generation");
+    println!("Compiled plan:        {compiled:.2?} per generation");
+    println!(
+        "Speedup:              {:.2}x",
+        baseline.as_secs_f64() / compiled.as_secs_f64()
+    );
+
+    // Batch generation across all cores
+    let parallel_generator = DataGenerator::from_value(config)?.with_threads(0);
+    let start = Instant::now();
+    let _ = parallel_generator.generate_batch(iterations)?;
+    let parallel = start.elapsed();
+
+    println!("\n=== Parallel Batch Generation ===");
+    println!("Batch of {iterations}: {parallel:.2?}");
+    println!(
+        "Speedup over sequential: {:.2}x",
+        duration.as_secs_f64() / parallel.as_secs_f64()
+    );
+
     let sample = generator.generate()?;
     println!(
         "\nSample output size: {} bytes",
@@ -414,3 +451,27 @@ fn main() -> Result<(), DataFakeError> {
 
     Ok(())
 }
+
+/// Evaluates `schema` the way generators did before configs were compiled into
+/// an execution plan: each single-key operator object is compiled and evaluated
+/// on its own, on every call.
+fn evaluate_per_leaf(schema: &Value, context: &GenerationContext) -> Result<Value, DataFakeError> {
+    match schema {
+        Value::Object(obj)
+            if obj.len() == 1 && obj.keys().all(|key| Engine::is_jsonlogic_operator(key)) =>
+        {
+            Engine::evaluate(schema, context)
+        }
+        Value::Object(obj) => obj
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), evaluate_per_leaf(value, context)?)))
+            .collect::<Result<Map<_, _>, _>>()
+            .map(Value::Object),
+        Value::Array(arr) => arr
+            .iter()
+            .map(|item| evaluate_per_leaf(item, context))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        _ => Ok(schema.clone()),
+    }
+}
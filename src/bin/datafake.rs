@@ -0,0 +1,558 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use datafake_rs::import::{import_json_schema, infer_config};
+use datafake_rs::output::{
+    ArrayHandling, CsvOptions, CsvWriter, JsonArrayWriter, NdjsonWriter, SqlDialect, SqlOptions,
+    SqlStatement, SqlWriter,
+};
+use datafake_rs::{ConfigParser, DataGenerator, DatasetGenerator, RecordWriter};
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Generate mock JSON data from datafake-rs config files.
+#[derive(Parser)]
+#[command(name = "datafake", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate records from a config file
+    Generate(GenerateArgs),
+    /// Check config files for errors without generating data
+    Validate {
+        /// Config files to check
+        #[arg(required = true)]
+        configs: Vec<PathBuf>,
+    },
+    /// Convert a JSON Schema into a config
+    Import {
+        /// JSON Schema file, or `-` to read from stdin
+        schema: PathBuf,
+
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Propose a config from sample records
+    Infer {
+        /// A JSON array of records or NDJSON, or `-` to read from stdin
+        samples: PathBuf,
+
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Print a JSON Schema describing the records a config generates
+    Schema {
+        /// Config file, or `-` to read from stdin
+        config: PathBuf,
+
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Config file, or `-` to read from stdin
+    config: PathBuf,
+
+    /// Number of records to generate (datasets use each entity's `count`)
+    #[arg(short = 'n', long, default_value_t = 1)]
+    count: usize,
+
+    /// Seed for reproducible output (overrides `metadata.seed`)
+    #[arg(short, long)]
+    seed: Option<u64>,
+
+    /// Index of the first record, to generate one shard of a seeded dataset
+    #[arg(long, default_value_t = 0)]
+    start: u64,
+
+    /// Worker threads (`0` uses all cores); output is the same for any count
+    #[arg(short = 'j', long, default_value_t = 1)]
+    threads: usize,
+
+    /// Output file (defaults to stdout); for datasets, a directory that gets one
+    /// file per entity
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Json)]
+    format: Format,
+
+    /// CSV field delimiter
+    #[arg(long, default_value_t = ',')]
+    delimiter: char,
+
+    /// Omit the CSV header row
+    #[arg(long)]
+    no_header: bool,
+
+    /// How arrays are written to CSV rows
+    #[arg(long, value_enum, default_value_t = Arrays::Json)]
+    arrays: Arrays,
+
+    /// Table to insert into (required for SQL output)
+    #[arg(long)]
+    table: Option<String>,
+
+    /// SQL dialect
+    #[arg(long, value_enum, default_value_t = Dialect::Postgres)]
+    dialect: Dialect,
+
+    /// Write a Postgres COPY block instead of INSERT statements
+    #[arg(long)]
+    copy: bool,
+
+    /// Rows per INSERT statement
+    #[arg(long, default_value_t = 100)]
+    batch_size: usize,
+
+    /// Emit a CREATE TABLE inferred from the schema before the rows
+    #[arg(long)]
+    create_table: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// A pretty-printed JSON array
+    Json,
+    /// One JSON record per line
+    Ndjson,
+    /// CSV with nested fields flattened into dotted columns
+    Csv,
+    /// SQL INSERT statements, or a Postgres COPY block with `--copy`
+    Sql,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Arrays {
+    /// Encode arrays as JSON strings
+    Json,
+    /// Join elements with `|`
+    Join,
+    /// Write one row per array element
+    Explode,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Dialect {
+    Postgres,
+    Mysql,
+    Sqlite,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Generate(args) => generate(&args),
+        Command::Validate { configs } => validate(&configs),
+        Command::Import { schema, output } => import(&schema, output.as_deref()),
+        Command::Infer { samples, output } => infer(&samples, output.as_deref()),
+        Command::Schema { config, output } => schema(&config, output.as_deref()),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn generate(args: &GenerateArgs) -> Result<ExitCode, Box<dyn Error>> {
+    let json = read_config(&args.config)?;
+    if is_dataset(&json) {
+        return generate_dataset(&json, args);
+    }
+
+    let mut generator = DataGenerator::from_json(&json)?;
+    if let Some(seed) = args.seed {
+        generator = generator.with_seed(seed);
+    }
+    generator = generator.with_threads(args.threads).starting_at(args.start);
+
+    let output: BufWriter<Box<dyn Write>> = BufWriter::new(match &args.output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    });
+    let mut sink = record_writer(output, args, &generator.config().schema)?;
+    generator.write_to(sink.as_mut(), args.count)?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Writes every entity of a dataset: one file per entity in the `--output`
+/// directory, or to stdout as a single JSON object or SQL script.
+fn generate_dataset(json: &str, args: &GenerateArgs) -> Result<ExitCode, Box<dyn Error>> {
+    let mut generator = DatasetGenerator::from_json(json)?;
+    if let Some(seed) = args.seed {
+        generator = generator.with_seed(seed);
+    }
+    generator = generator.with_threads(args.threads);
+
+    match (&args.output, args.format) {
+        (Some(dir), format) => {
+            fs::create_dir_all(dir)?;
+            let extension = match format {
+                Format::Json => "json",
+                Format::Ndjson => "ndjson",
+                Format::Csv => "csv",
+                Format::Sql => "sql",
+            };
+            generator.write_to(|entity, schema| {
+                let file = File::create(dir.join(format!("{entity}.{extension}")))?;
+                entity_writer(BufWriter::new(file), args, entity, schema)
+            })?;
+        }
+        (None, Format::Json) => {
+            let dataset: serde_json::Map<String, serde_json::Value> = generator
+                .generate()?
+                .into_iter()
+                .map(|(entity, records)| (entity, records.into()))
+                .collect();
+            let mut stdout = io::stdout().lock();
+            serde_json::to_writer_pretty(&mut stdout, &dataset)?;
+            writeln!(stdout)?;
+        }
+        (None, Format::Sql) => generator.write_to(|entity, schema| {
+            entity_writer(BufWriter::new(io::stdout()), args, entity, schema)
+        })?,
+        (None, _) => return Err("CSV and NDJSON datasets need --output <dir>".into()),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Whether a config describes a multi-entity dataset rather than a single schema.
+fn is_dataset(json: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(json)
+        .is_ok_and(|config| config.get("entities").is_some() && config.get("schema").is_none())
+}
+
+fn validate(configs: &[PathBuf]) -> Result<ExitCode, Box<dyn Error>> {
+    let mut failed = false;
+    for path in configs {
+        let parsed = read_config(path).and_then(|json| {
+            let warnings = if is_dataset(&json) {
+                ConfigParser::dataset_warnings(&ConfigParser::parse_dataset(&json)?)
+            } else {
+                ConfigParser::warnings(&ConfigParser::parse(&json)?)
+            };
+            Ok(warnings)
+        });
+        match parsed {
+            Ok(warnings) => {
+                for warning in warnings {
+                    eprintln!("warning: {}: {warning}", path.display());
+                }
+                println!("ok: {}", path.display());
+            }
+            Err(e) => {
+                eprintln!("invalid: {}: {e}", path.display());
+                failed = true;
+            }
+        }
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn import(schema: &Path, output: Option<&Path>) -> Result<ExitCode, Box<dyn Error>> {
+    let schema: serde_json::Value = serde_json::from_str(&read_config(schema)?)?;
+    let config = import_json_schema(&schema)?;
+    write_pretty(&config, output)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn infer(samples: &Path, output: Option<&Path>) -> Result<ExitCode, Box<dyn Error>> {
+    let json = read_config(samples)?;
+    let mut records = serde_json::Deserializer::from_str(&json)
+        .into_iter::<serde_json::Value>()
+        .collect::<Result<Vec<_>, _>>()?;
+    // A single array holds the records; anything else is NDJSON
+    if let [serde_json::Value::Array(array)] = &mut records[..] {
+        records = std::mem::take(array);
+    }
+    let config = infer_config(&records)?;
+    write_pretty(&config, output)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn schema(config: &Path, output: Option<&Path>) -> Result<ExitCode, Box<dyn Error>> {
+    let json = read_config(config)?;
+    if is_dataset(&json) {
+        return Err("schema export takes a single config, not a dataset".into());
+    }
+    let generator = DataGenerator::from_json(&json)?;
+    write_pretty(&generator.json_schema(), output)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn write_pretty<T: serde::Serialize>(
+    value: &T,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let mut output: BufWriter<Box<dyn Write>> = BufWriter::new(match output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    });
+    serde_json::to_writer_pretty(&mut output, value)?;
+    writeln!(output)?;
+    output.flush()?;
+    Ok(())
+}
+
+fn read_config(path: &Path) -> Result<String, Box<dyn Error>> {
+    if path.as_os_str() == "-" {
+        let mut json = String::new();
+        io::stdin().read_to_string(&mut json)?;
+        return Ok(json);
+    }
+    fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()).into())
+}
+
+fn record_writer<W: Write + 'static>(
+    output: W,
+    args: &GenerateArgs,
+    schema: &serde_json::Value,
+) -> Result<Box<dyn RecordWriter>, Box<dyn Error>> {
+    Ok(match args.format {
+        Format::Json => Box::new(JsonArrayWriter::new(output)),
+        Format::Ndjson => Box::new(NdjsonWriter::new(output)),
+        Format::Csv => Box::new(CsvWriter::new(output, schema, csv_options(args)?)),
+        Format::Sql => {
+            let table = args
+                .table
+                .clone()
+                .ok_or("--table is required for SQL output")?;
+            Box::new(SqlWriter::new(output, schema, sql_options(args, table))?)
+        }
+    })
+}
+
+/// The writer for one entity of a dataset; SQL tables are named after the entity.
+fn entity_writer<W: Write + 'static>(
+    output: W,
+    args: &GenerateArgs,
+    entity: &str,
+    schema: &serde_json::Value,
+) -> datafake_rs::Result<Box<dyn RecordWriter>> {
+    let options = |e: Box<dyn Error>| datafake_rs::DataFakeError::InvalidConfig(e.to_string());
+    Ok(match args.format {
+        Format::Json => Box::new(JsonArrayWriter::new(output)),
+        Format::Ndjson => Box::new(NdjsonWriter::new(output)),
+        Format::Csv => Box::new(CsvWriter::new(
+            output,
+            schema,
+            csv_options(args).map_err(options)?,
+        )),
+        Format::Sql => Box::new(SqlWriter::new(
+            output,
+            schema,
+            sql_options(args, entity.to_string()),
+        )?),
+    })
+}
+
+fn sql_options(args: &GenerateArgs, table: String) -> SqlOptions {
+    SqlOptions {
+        dialect: match args.dialect {
+            Dialect::Postgres => SqlDialect::Postgres,
+            Dialect::Mysql => SqlDialect::MySql,
+            Dialect::Sqlite => SqlDialect::Sqlite,
+        },
+        statement: if args.copy {
+            SqlStatement::Copy
+        } else {
+            SqlStatement::Insert
+        },
+        batch_size: args.batch_size,
+        create_table: args.create_table,
+        ..SqlOptions::new(table)
+    }
+}
+
+fn csv_options(args: &GenerateArgs) -> Result<CsvOptions, Box<dyn Error>> {
+    let delimiter = u8::try_from(args.delimiter)
+        .ok()
+        .filter(u8::is_ascii)
+        .ok_or_else(|| {
+            format!(
+                "CSV delimiter must be a single ASCII character, got {:?}",
+                args.delimiter
+            )
+        })?;
+    Ok(CsvOptions {
+        delimiter,
+        header: !args.no_header,
+        arrays: match args.arrays {
+            Arrays::Json => ArrayHandling::Json,
+            Arrays::Join => ArrayHandling::Join("|".to_string()),
+            Arrays::Explode => ArrayHandling::Explode,
+        },
+        ..CsvOptions::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn write(generator: &DataGenerator, extra_args: &[&str]) -> String {
+        let cli = Cli::try_parse_from(
+            ["datafake", "generate", "config.json"]
+                .iter()
+                .chain(extra_args),
+        )
+        .unwrap();
+        let Command::Generate(args) = cli.command else {
+            panic!("expected the generate subcommand");
+        };
+
+        let output = SharedBuffer::default();
+        let mut sink = record_writer(output.clone(), &args, &generator.config().schema).unwrap();
+        generator.write_to(sink.as_mut(), 3).unwrap();
+        output.contents()
+    }
+
+    #[test]
+    fn test_write_formats() {
+        let generator = DataGenerator::from_json(
+            r#"{"schema": {"id": {"fake": ["uuid"]}, "user": {"age": {"fake": ["u8", 18, 65]}}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(write(&generator, &["-f", "ndjson"]).lines().count(), 3);
+
+        let parsed: serde_json::Value = serde_json::from_str(&write(&generator, &[])).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), 3);
+
+        let csv = write(&generator, &["-f", "csv", "--delimiter", ";"]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "id;user.age");
+
+        let csv = write(&generator, &["-f", "csv", "--no-header"]);
+        assert_eq!(csv.lines().count(), 3);
+
+        let sql = write(
+            &generator,
+            &[
+                "-f",
+                "sql",
+                "--table",
+                "users",
+                "--dialect",
+                "mysql",
+                "--batch-size",
+                "2",
+            ],
+        );
+        assert_eq!(sql.matches("INSERT INTO `users`").count(), 2);
+
+        let copy = write(&generator, &["-f", "sql", "--table", "users", "--copy"]);
+        assert!(copy.starts_with("COPY \"users\""));
+    }
+
+    #[test]
+    fn test_dataset_output() {
+        let dataset = r#"{
+            "entities": {
+                "users": {"count": 2, "schema": {"id": {"sequence": {}}}},
+                "orders": {"count": 3, "schema": {"id": {"fake": ["uuid"]}, "user_id": {"ref": "users"}}}
+            }
+        }"#;
+        assert!(is_dataset(dataset));
+        assert!(!is_dataset(r#"{"schema": {"entities": 1}}"#));
+
+        let dir = std::env::temp_dir().join(format!("datafake-dataset-{}", std::process::id()));
+        let cli = Cli::try_parse_from([
+            "datafake",
+            "generate",
+            "dataset.json",
+            "-f",
+            "csv",
+            "-o",
+            dir.to_str().unwrap(),
+        ])
+        .unwrap();
+        let Command::Generate(args) = cli.command else {
+            panic!("expected the generate subcommand");
+        };
+        generate_dataset(dataset, &args).unwrap();
+
+        let users = fs::read_to_string(dir.join("users.csv")).unwrap();
+        let orders = fs::read_to_string(dir.join("orders.csv")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(users, "id\r\n1\r\n2\r\n");
+        assert_eq!(orders.lines().count(), 4);
+        assert!(
+            orders
+                .lines()
+                .skip(1)
+                .all(|line| line.ends_with(",1") || line.ends_with(",2"))
+        );
+    }
+
+    /// Cloneable in-memory writer, so tests can read what a boxed sink wrote.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.borrow().clone()).unwrap()
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_cli_arguments() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::try_parse_from([
+            "datafake",
+            "generate",
+            "users.json",
+            "-n",
+            "10",
+            "--seed",
+            "7",
+            "-f",
+            "ndjson",
+            "-j",
+            "4",
+        ])
+        .unwrap();
+        let Command::Generate(args) = cli.command else {
+            panic!("expected the generate subcommand");
+        };
+        assert_eq!(args.count, 10);
+        assert_eq!(args.seed, Some(7));
+        assert_eq!(args.threads, 4);
+        assert!(matches!(args.format, Format::Ndjson));
+    }
+}
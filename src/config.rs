@@ -1,7 +1,14 @@
-use crate::error::{DataFakeError, Result};
-use crate::types::{DataFakeConfig, GenerationContext};
+use crate::dataset;
+use crate::engine::Engine;
+use crate::error::{ConfigIssue, DataFakeError, Result};
+use crate::locale::Locale;
+use crate::operators::fake::LOCALIZED_METHODS;
+use crate::operators::finance::iban_countries;
+use crate::operators::{FakeOperator, RegexGenerator, datetime, distribution, registry};
+use crate::plan::{INDEX_VARIABLE, PlanNode, RECORD_VARIABLE, Sequence};
+use crate::types::{DataFakeConfig, DatasetConfig, GenerationContext, Metadata};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 pub struct ConfigParser;
 
@@ -22,85 +29,346 @@ impl ConfigParser {
         Ok(config)
     }
 
-    fn validate_config(config: &DataFakeConfig) -> Result<()> {
-        if config.schema.is_null() {
-            return Err(DataFakeError::InvalidConfig(
-                "Schema cannot be null".to_string(),
-            ));
-        }
+    pub fn parse_dataset(json_str: &str) -> Result<DatasetConfig> {
+        let config: DatasetConfig = serde_json::from_str(json_str)
+            .map_err(|e| DataFakeError::ConfigParse(format!("Failed to parse JSON: {e}")))?;
 
-        Self::validate_variables(&config.variables)?;
-        Self::validate_schema(&config.schema)?;
+        Self::validate_dataset(&config)?;
+        Ok(config)
+    }
 
-        Ok(())
+    pub fn parse_dataset_value(json_value: Value) -> Result<DatasetConfig> {
+        let config: DatasetConfig = serde_json::from_value(json_value)
+            .map_err(|e| DataFakeError::ConfigParse(format!("Failed to parse JSON value: {e}")))?;
+
+        Self::validate_dataset(&config)?;
+        Ok(config)
     }
 
-    fn validate_variables(variables: &HashMap<String, Value>) -> Result<()> {
-        for (name, value) in variables {
-            if name.is_empty() {
-                return Err(DataFakeError::InvalidConfig(
-                    "Variable name cannot be empty".to_string(),
+    /// Located problems that don't stop a config from generating, such as `var`
+    /// references to undeclared variables, which evaluate to `null`. Empty when
+    /// the config is invalid; [`ConfigParser::parse`] reports those errors.
+    pub fn warnings(config: &DataFakeConfig) -> Vec<ConfigIssue> {
+        Self::validate_config(config).unwrap_or_default()
+    }
+
+    /// Like [`ConfigParser::warnings`], for every entity of a dataset.
+    pub fn dataset_warnings(config: &DatasetConfig) -> Vec<ConfigIssue> {
+        Self::validate_dataset(config).unwrap_or_default()
+    }
+
+    /// Checks a dataset, returning its warnings.
+    fn validate_dataset(config: &DatasetConfig) -> Result<Vec<ConfigIssue>> {
+        let mut validator = Validator::default();
+        if let Some(metadata) = &config.metadata {
+            validator.at("metadata", |v| Self::check_metadata(v, metadata));
+        }
+
+        validator.at("entities", |v| {
+            if config.entities.is_empty() {
+                v.report(
+                    DataFakeError::InvalidConfig(
+                        "Dataset must define at least one entity".to_string(),
+                    ),
+                    None,
+                );
+            }
+            for (name, entity) in &config.entities {
+                v.at(name, |v| {
+                    if name.is_empty() {
+                        v.report(
+                            DataFakeError::InvalidConfig("Entity name cannot be empty".to_string()),
+                            None,
+                        );
+                    }
+                    if entity.primary_key.is_empty() {
+                        v.at("primary_key", |v| {
+                            v.report(
+                                DataFakeError::InvalidConfig(format!(
+                                    "Entity '{name}' primary key cannot be empty"
+                                )),
+                                None,
+                            );
+                        });
+                    }
+                    Self::check_generation(v, &entity.variables, &entity.schema);
+                });
+            }
+        });
+        // Ordering needs every ref to be well-formed
+        if !validator.issues.is_empty() {
+            return validator.finish();
+        }
+
+        // Referenced entities come first, so refs must not form a cycle
+        let order = match dataset::entity_order(config) {
+            Ok(order) => order,
+            Err(e) => {
+                validator.at("entities", |v| v.report(e, None));
+                return validator.finish();
+            }
+        };
+        for name in order {
+            let entity = &config.entities[name];
+            let referenced = config
+                .entities
+                .values()
+                .any(|other| dataset::references(other).contains(name));
+            if referenced
+                && let Value::Object(schema) = &entity.schema
+                && !schema.contains_key(&entity.primary_key)
+                // A single-key expression produces the whole record; it can't be checked here
+                && !(schema.len() == 1 && schema.keys().all(|key| PlanNode::is_expression_key(key)))
+            {
+                let error = DataFakeError::InvalidConfig(format!(
+                    "Entity '{name}' is referenced but its schema has no primary key field '{}'",
+                    entity.primary_key
                 ));
+                validator.at("entities", |v| {
+                    v.at(name, |v| v.at("schema", |v| v.report(error, None)));
+                });
             }
+        }
+        validator.finish()
+    }
+
+    /// Checks a config, returning its warnings.
+    fn validate_config(config: &DataFakeConfig) -> Result<Vec<ConfigIssue>> {
+        let mut validator = Validator::default();
+        if let Some(metadata) = &config.metadata {
+            validator.at("metadata", |v| Self::check_metadata(v, metadata));
+        }
+        Self::check_generation(&mut validator, &config.variables, &config.schema);
+        validator.finish()
+    }
 
-            if value.is_null() {
-                return Err(DataFakeError::InvalidConfig(format!(
-                    "Variable '{name}' cannot be null"
-                )));
+    fn check_metadata(v: &mut Validator, metadata: &Metadata) {
+        if let Some(locale) = &metadata.locale {
+            v.at("locale", |v| {
+                let value = Value::from(locale.as_str());
+                v.check(locale.parse::<Locale>().map(|_| ()), Some(&value));
+            });
+        }
+        if let Some(now) = &metadata.now {
+            v.at("now", |v| {
+                let value = Value::from(now.as_str());
+                v.check(datetime::parse_anchor(now).map(|_| ()), Some(&value));
+            });
+        }
+    }
+
+    /// Checks the variables and schema of a config or dataset entity.
+    fn check_generation(v: &mut Validator, variables: &HashMap<String, Value>, schema: &Value) {
+        v.variables = variables.keys().cloned().collect();
+        v.at("variables", |v| Self::check_variables(v, variables));
+        v.at("schema", |v| {
+            if schema.is_null() {
+                v.report(
+                    DataFakeError::InvalidConfig("Schema cannot be null".to_string()),
+                    None,
+                );
+            } else {
+                Self::check_schema(v, schema);
             }
+        });
+    }
 
-            Self::validate_jsonlogic_expression(value)?;
+    fn check_variables(v: &mut Validator, variables: &HashMap<String, Value>) {
+        let sorted: BTreeMap<&String, &Value> = variables.iter().collect();
+        for (name, value) in sorted {
+            v.at(name, |v| {
+                if name.is_empty() {
+                    v.report(
+                        DataFakeError::InvalidConfig("Variable name cannot be empty".to_string()),
+                        None,
+                    );
+                } else if value.is_null() {
+                    v.report(
+                        DataFakeError::InvalidConfig(format!("Variable '{name}' cannot be null")),
+                        None,
+                    );
+                } else {
+                    Self::check_logic(v, value);
+                }
+            });
         }
-        Ok(())
+
+        // Variables may reference each other, but not in a cycle
+        v.check(Engine::sort_variables(variables).map(|_| ()), None);
     }
 
-    fn validate_schema(schema: &Value) -> Result<()> {
+    fn check_schema(v: &mut Validator, schema: &Value) {
         match schema {
             Value::Object(map) => {
-                // Check if this is a JSONLogic expression
-                if map.contains_key("fake") || map.contains_key("var") {
-                    Self::validate_jsonlogic_expression(schema)?;
-                } else {
-                    // Regular object, validate each property
-                    for (key, value) in map {
-                        if key.is_empty() {
-                            return Err(DataFakeError::InvalidConfig(
-                                "Schema key cannot be empty".to_string(),
-                            ));
+                let construct = (map.len() == 1).then(|| map.iter().next()).flatten();
+                match construct {
+                    Some((key, _)) if Engine::is_jsonlogic_operator(key) => {
+                        Self::check_logic(v, schema);
+                    }
+                    Some((key, args)) if key == "repeat" => match PlanNode::repeat_args(args) {
+                        Ok((count, item)) => v.at(key, |v| {
+                            v.at("0", |v| {
+                                v.check(PlanNode::repeat_bounds(count).map(|_| ()), Some(count));
+                            });
+                            v.at("1", |v| Self::check_schema(v, item));
+                        }),
+                        Err(e) => v.report(e, Some(schema)),
+                    },
+                    Some((key, args)) if key == "unique" => match PlanNode::unique_args(args) {
+                        Ok((item, _)) => v.within(key, args, item, Self::check_schema),
+                        Err(e) => v.report(e, Some(schema)),
+                    },
+                    Some((key, args)) if key == "optional" || key == "nullable" => {
+                        match PlanNode::probability_args(key, args) {
+                            Ok((_, item)) => v.within(key, args, item, Self::check_schema),
+                            Err(e) => v.report(e, Some(schema)),
+                        }
+                    }
+                    Some((key, args)) if key == "sequence" => {
+                        v.check(Sequence::parse(args).map(|_| ()), Some(schema));
+                    }
+                    Some((key, args)) if key == "ref" => {
+                        v.check(PlanNode::reference_args(args).map(|_| ()), Some(schema));
+                    }
+                    Some((key, args)) if key == "one_of" => match PlanNode::one_of_args(args) {
+                        Ok(items) => v.at(key, |v| {
+                            for (i, item) in items.iter().enumerate() {
+                                v.at(&i.to_string(), |v| Self::check_schema(v, item));
+                            }
+                        }),
+                        Err(e) => v.report(e, Some(schema)),
+                    },
+                    _ => {
+                        // Regular object, validate each property
+                        for (key, value) in map {
+                            v.at(key, |v| {
+                                if key.is_empty() {
+                                    v.report(
+                                        DataFakeError::InvalidConfig(
+                                            "Schema key cannot be empty".to_string(),
+                                        ),
+                                        None,
+                                    );
+                                }
+                                Self::check_schema(v, value);
+                            });
                         }
-                        Self::validate_schema(value)?;
                     }
                 }
             }
             Value::Array(arr) => {
-                for item in arr {
-                    Self::validate_schema(item)?;
+                for (i, item) in arr.iter().enumerate() {
+                    v.at(&i.to_string(), |v| Self::check_schema(v, item));
                 }
             }
-            Value::Null => {
-                return Err(DataFakeError::InvalidConfig(
-                    "Schema values cannot be null".to_string(),
-                ));
-            }
+            Value::Null => v.report(
+                DataFakeError::InvalidConfig("Schema values cannot be null".to_string()),
+                None,
+            ),
             _ => {}
         }
-        Ok(())
     }
 
-    fn validate_jsonlogic_expression(value: &Value) -> Result<()> {
-        if let Value::Object(map) = value {
-            if map.contains_key("fake") {
-                Self::validate_fake_operator(map.get("fake").unwrap())?;
-            } else if map.contains_key("var")
-                && let Some(Value::String(var_name)) = map.get("var")
-                && var_name.is_empty()
-            {
-                return Err(DataFakeError::InvalidConfig(
-                    "Variable reference cannot be empty".to_string(),
-                ));
+    /// Checks a JSONLogic expression used as a variable or schema value, which
+    /// can't be a bare `{"var": ""}`.
+    fn check_logic(v: &mut Validator, value: &Value) {
+        if value.get("var").and_then(Value::as_str) == Some("") {
+            return v.report(
+                DataFakeError::InvalidConfig("Variable reference cannot be empty".to_string()),
+                Some(value),
+            );
+        }
+        Self::check_expression(v, value, false);
+    }
+
+    /// Checks the `fake` calls and `var` references anywhere in a JSONLogic
+    /// expression. Inside iterating operators like `map`, `var` reads the current
+    /// element, so references aren't checked against the variables there.
+    fn check_expression(v: &mut Validator, value: &Value, iterating: bool) {
+        match value {
+            Value::Object(map) => {
+                if map.len() == 1
+                    && let Some(args) = map.get("fake")
+                {
+                    return Self::check_fake(v, value, args);
+                }
+                if map.len() == 1
+                    && let Some(target) = map.get("var")
+                {
+                    return Self::check_var(v, value, target, iterating);
+                }
+                for (key, args) in map {
+                    let iterates = matches!(
+                        key.as_str(),
+                        "map" | "filter" | "reduce" | "all" | "none" | "some"
+                    );
+                    v.at(key, |v| match args {
+                        // Only the mapper (the second argument) runs per element;
+                        // the array and a `reduce` initial value do not
+                        Value::Array(items) if iterates => {
+                            for (i, item) in items.iter().enumerate() {
+                                v.at(&i.to_string(), |v| {
+                                    Self::check_expression(v, item, iterating || i == 1)
+                                });
+                            }
+                        }
+                        args => Self::check_expression(v, args, iterating),
+                    });
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    v.at(&i.to_string(), |v| {
+                        Self::check_expression(v, item, iterating)
+                    });
+                }
             }
+            _ => {}
         }
-        Ok(())
+    }
+
+    fn check_fake(v: &mut Validator, expression: &Value, args: &Value) {
+        if let Err(e) = Self::validate_fake_operator(args) {
+            let suggestion = match args.get(0).and_then(Value::as_str) {
+                Some(method) if registry::lookup(method).is_none() => {
+                    suggest(method, registry::METHODS.iter().map(|spec| spec.name))
+                }
+                _ => None,
+            };
+            v.report_with_suggestion(e, Some(expression), suggestion);
+        }
+    }
+
+    fn check_var(v: &mut Validator, expression: &Value, target: &Value, iterating: bool) {
+        let path = match target {
+            Value::Array(arr) => arr.first(),
+            other => Some(other),
+        };
+        // An empty path reads the whole data
+        let Some(Value::String(path)) = path else {
+            return;
+        };
+        if iterating || path.is_empty() {
+            return;
+        }
+
+        let name = path.split('.').next().unwrap_or(path);
+        if name == INDEX_VARIABLE || name == RECORD_VARIABLE || v.variables.contains(name) {
+            return;
+        }
+        let candidates: Vec<&str> = v
+            .variables
+            .iter()
+            .map(String::as_str)
+            .chain([INDEX_VARIABLE, RECORD_VARIABLE])
+            .collect();
+        // Unknown variables evaluate to `null`, which a config may rely on
+        let suggestion = suggest(name, candidates);
+        v.warn(
+            DataFakeError::VariableNotFound(name.to_string()),
+            Some(expression),
+            suggestion,
+        );
     }
 
     fn validate_fake_operator(args: &Value) -> Result<()> {
@@ -118,14 +386,32 @@ impl ConfigParser {
                             "Fake method name cannot be empty".to_string(),
                         ));
                     }
+                    registry::lookup(method)
+                        .ok_or_else(|| {
+                            DataFakeError::InvalidConfig(format!("Unknown fake method '{method}'"))
+                        })?
+                        .check(&arr[1..])?;
 
                     match method.as_str() {
                         "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" | "f32"
                         | "f64" => {
+                            let arr = match arr.split_last() {
+                                Some((options, rest)) if options.is_object() => {
+                                    distribution::validate(arr)?;
+                                    rest
+                                }
+                                _ => arr.as_slice(),
+                            };
                             if arr.len() == 3 {
                                 let min = Self::extract_number(arr.get(1))?;
                                 let max = Self::extract_number(arr.get(2))?;
-                                if min > max {
+                                // Uniform float sampling needs a span the type can hold
+                                let span = if method == "f32" {
+                                    (max as f32 - min as f32) as f64
+                                } else {
+                                    max - min
+                                };
+                                if min > max || !span.is_finite() {
                                     return Err(DataFakeError::InvalidRange { min, max });
                                 }
                             } else if arr.len() != 1 {
@@ -134,6 +420,38 @@ impl ConfigParser {
                                 )));
                             }
                         }
+                        "datetime" | "iso8601_datetime" | "date" => datetime::validate(arr)?,
+                        "password" | "sentence" | "paragraph" | "alphanumeric" => {
+                            Self::validate_length_range(method, arr)?;
+                        }
+                        "weighted" | "weighted_choice" => {
+                            let (_, weights) = FakeOperator::weighted_options(arr)?;
+                            let total: f64 = weights.iter().sum();
+                            if !(total.is_finite() && total > 0.0) {
+                                return Err(DataFakeError::InvalidConfig(format!(
+                                    "weighted weights must add up to a finite positive number, got {total}"
+                                )));
+                            }
+                        }
+                        "iban" => {
+                            if let Some(Value::String(country)) = arr.get(1)
+                                && !iban_countries().any(|c| c.eq_ignore_ascii_case(country))
+                            {
+                                return Err(DataFakeError::InvalidConfig(format!(
+                                    "Unsupported IBAN country: {country}"
+                                )));
+                            }
+                        }
+                        "regex" => {
+                            if let Some(Value::String(pattern)) = arr.get(1) {
+                                RegexGenerator::parse(pattern)?;
+                            }
+                        }
+                        _ if LOCALIZED_METHODS.contains(&method.as_str()) => {
+                            if let Some(Value::String(locale)) = arr.get(1) {
+                                locale.parse::<Locale>()?;
+                            }
+                        }
                         _ => {}
                     }
                 } else {
@@ -151,6 +469,30 @@ impl ConfigParser {
         Ok(())
     }
 
+    /// Checks the `min, max` arguments of methods generating text of a random
+    /// length, whose defaults apply to missing bounds. The upper bound is
+    /// exclusive except for `alphanumeric`.
+    fn validate_length_range(method: &str, args: &[Value]) -> Result<()> {
+        let min = args.get(1).and_then(Value::as_u64);
+        let max = args.get(2).and_then(Value::as_u64);
+        let (min, max, exclusive) = match method {
+            "password" => (min.unwrap_or(8), max.unwrap_or(20), true),
+            "sentence" => (min.unwrap_or(4), max.unwrap_or(10), true),
+            "paragraph" => (min.unwrap_or(3), max.unwrap_or(7), true),
+            _ => {
+                let min = min.unwrap_or(10);
+                (min, max.unwrap_or(min), false)
+            }
+        };
+        if min > max || (exclusive && min == max) {
+            return Err(DataFakeError::InvalidConfig(format!(
+                "Fake method '{method}' needs min < max{}, got {min} and {max}",
+                if exclusive { "" } else { " or equal" }
+            )));
+        }
+        Ok(())
+    }
+
     fn extract_number(value: Option<&Value>) -> Result<f64> {
         match value {
             Some(Value::Number(n)) => n
@@ -167,6 +509,140 @@ impl ConfigParser {
     }
 }
 
+/// Collects every problem found while checking a config, each located by the
+/// JSON pointer of the value being checked.
+#[derive(Default)]
+struct Validator {
+    path: Vec<String>,
+    issues: Vec<ConfigIssue>,
+    warnings: Vec<ConfigIssue>,
+    /// Names that `var` may reference in the config being checked
+    variables: BTreeSet<String>,
+}
+
+impl Validator {
+    /// Runs `check` one level deeper, under `key`.
+    fn at(&mut self, key: &str, check: impl FnOnce(&mut Self)) {
+        self.path.push(key.to_string());
+        check(self);
+        self.path.pop();
+    }
+
+    /// Runs `check` on `item`, one of the arguments of the `key` construct.
+    fn within(
+        &mut self,
+        key: &str,
+        args: &Value,
+        item: &Value,
+        check: impl FnOnce(&mut Self, &Value),
+    ) {
+        self.at(key, |v| match args {
+            Value::Array(arr) => match arr.iter().position(|arg| std::ptr::eq(arg, item)) {
+                Some(i) => v.at(&i.to_string(), |v| check(v, item)),
+                None => check(v, item),
+            },
+            _ => check(v, item),
+        });
+    }
+
+    fn check(&mut self, result: Result<()>, value: Option<&Value>) {
+        if let Err(e) = result {
+            self.report(e, value);
+        }
+    }
+
+    fn report(&mut self, error: DataFakeError, value: Option<&Value>) {
+        self.report_with_suggestion(error, value, None);
+    }
+
+    fn report_with_suggestion(
+        &mut self,
+        error: DataFakeError,
+        value: Option<&Value>,
+        suggestion: Option<String>,
+    ) {
+        let issue = self.issue(error, value, suggestion);
+        self.issues.push(issue);
+    }
+
+    /// Records a problem that doesn't make the config invalid.
+    fn warn(&mut self, error: DataFakeError, value: Option<&Value>, suggestion: Option<String>) {
+        let issue = self.issue(error, value, suggestion);
+        self.warnings.push(issue);
+    }
+
+    fn issue(
+        &self,
+        error: DataFakeError,
+        value: Option<&Value>,
+        suggestion: Option<String>,
+    ) -> ConfigIssue {
+        let path = self
+            .path
+            .iter()
+            .map(|key| format!("/{}", key.replace('~', "~0").replace('/', "~1")))
+            .collect();
+        ConfigIssue {
+            path,
+            snippet: value.map(snippet),
+            suggestion,
+            error,
+        }
+    }
+
+    /// The warnings, or every error found as one [`DataFakeError::Validation`].
+    fn finish(self) -> Result<Vec<ConfigIssue>> {
+        if self.issues.is_empty() {
+            Ok(self.warnings)
+        } else {
+            Err(DataFakeError::Validation(self.issues))
+        }
+    }
+}
+
+/// Compact JSON of `value`, shortened to keep messages readable.
+fn snippet(value: &Value) -> String {
+    const MAX_CHARS: usize = 80;
+    let json = value.to_string();
+    match json.char_indices().nth(MAX_CHARS) {
+        Some((end, _)) => format!("{}…", &json[..end]),
+        None => json,
+    }
+}
+
+/// The candidate closest to `name`, if it's near enough to be a misspelling.
+fn suggest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let limit = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Edits (insertions, deletions, substitutions and swaps of adjacent
+/// characters) needed to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -252,6 +728,46 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_numeric_distribution_validation() {
+        let valid = r#"{
+            "schema": {
+                "score": {"fake": ["f64", 0, 100, {"distribution": "normal", "mean": 70, "std_dev": 12, "decimals": 1}]},
+                "orders": {"fake": ["u32", {"distribution": "poisson", "lambda": 3}]}
+            }
+        }"#;
+        assert!(ConfigParser::parse(valid).is_ok());
+
+        for fake in [
+            r#"["f64", 100, 0, {"decimals": 2}]"#,
+            r#"["f64", 0, 100, {"distribution": "normal", "mean": 70}]"#,
+            r#"["u32", {"distribution": "zipf", "n": 10, "s": -1}]"#,
+            r#"["u32", 1, {"distribution": "exponential", "lambda": 1}]"#,
+            r#"["u8", 300, 400, {"distribution": "normal", "mean": 0, "std_dev": 1}]"#,
+            r#"["f64", -1e308, 1e308, {"decimals": 2}]"#,
+            r#"["u8", 200, 300]"#,
+            r#"["i8", 0.5, 10]"#,
+            r#"["f64", -1e308, 1e308]"#,
+            r#"["f32", -3e38, 3e38]"#,
+        ] {
+            let config = format!(r#"{{"schema": {{"value": {{"fake": {fake}}}}}}}"#);
+            assert!(ConfigParser::parse(&config).is_err(), "{fake}");
+        }
+    }
+
+    #[test]
+    fn test_sequence_validation() {
+        let valid =
+            r#"{"schema": {"id": {"sequence": {"start": 1000, "step": 10, "prefix": "ORD-"}}}}"#;
+        assert!(ConfigParser::parse(valid).is_ok());
+
+        let invalid = r#"{"schema": {"id": {"sequence": {"step": "one"}}}}"#;
+        assert!(matches!(
+            ConfigParser::parse(invalid).unwrap_err().issues()[0].error,
+            DataFakeError::InvalidConfig(_)
+        ));
+    }
+
     #[test]
     fn test_empty_variable_name() {
         let config_json = r#"{
@@ -290,4 +806,225 @@ mod tests {
         let result = ConfigParser::parse(config_json);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_circular_variable_reference() {
+        let config_json = r#"{
+            "variables": {
+                "a": {"cat": [{"var": "b"}, "x"]},
+                "b": {"cat": [{"var": "c"}, "y"]},
+                "c": {"var": "a"}
+            },
+            "schema": {"value": {"var": "a"}}
+        }"#;
+
+        let err = ConfigParser::parse(config_json).unwrap_err();
+        assert_eq!(err.issues()[0].path, "/variables");
+        assert!(matches!(
+            &err.issues()[0].error,
+            DataFakeError::CircularVariableReference(cycle) if cycle == "a -> b -> c -> a"
+        ));
+    }
+
+    #[test]
+    fn test_repeat_validation() {
+        let valid = r#"{
+            "schema": {
+                "orders": {"repeat": [[0, 20], {"id": {"fake": ["uuid"]}}]}
+            }
+        }"#;
+        assert!(ConfigParser::parse(valid).is_ok());
+
+        let invalid_range = r#"{
+            "schema": {
+                "orders": {"repeat": [[20, 0], {"id": {"fake": ["uuid"]}}]}
+            }
+        }"#;
+        assert!(ConfigParser::parse(invalid_range).is_err());
+
+        let invalid_item = r#"{
+            "schema": {
+                "orders": {"repeat": [2, {"id": {"fake": []}}]}
+            }
+        }"#;
+        assert!(ConfigParser::parse(invalid_item).is_err());
+    }
+
+    #[test]
+    fn test_unique_validation() {
+        let valid = r#"{
+            "schema": {
+                "email": {"unique": {"fake": ["email"]}},
+                "id": {"unique": [{"fake": ["u32"]}, 1000]}
+            }
+        }"#;
+        assert!(ConfigParser::parse(valid).is_ok());
+
+        let zero_attempts = r#"{"schema": {"id": {"unique": [{"fake": ["u32"]}, 0]}}}"#;
+        assert!(ConfigParser::parse(zero_attempts).is_err());
+
+        let invalid_item = r#"{"schema": {"id": {"unique": {"fake": []}}}}"#;
+        assert!(ConfigParser::parse(invalid_item).is_err());
+    }
+
+    #[test]
+    fn test_optional_and_weighted_validation() {
+        let valid = r#"{
+            "schema": {
+                "status": {"fake": ["weighted", ["active", 90], ["deleted", 10]]},
+                "nickname": {"optional": [0.3, {"fake": ["username"]}]},
+                "phone": {"nullable": [0.1, {"fake": ["phone_number"]}]}
+            }
+        }"#;
+        assert!(ConfigParser::parse(valid).is_ok());
+
+        for invalid in [
+            r#"{"schema": {"status": {"fake": ["weighted", ["active"]]}}}"#,
+            r#"{"schema": {"status": {"fake": ["weighted", ["a", 1e308], ["b", 1e308]]}}}"#,
+            r#"{"schema": {"nickname": {"optional": [2, "x"]}}}"#,
+            r#"{"schema": {"phone": {"nullable": [0.5, {"fake": []}]}}}"#,
+        ] {
+            assert!(ConfigParser::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_locale_validation() {
+        let valid = r#"{
+            "metadata": {"locale": "fr_FR"},
+            "schema": {"name": {"fake": ["name", "de_DE"]}}
+        }"#;
+        assert!(ConfigParser::parse(valid).is_ok());
+
+        let unknown_default = r#"{
+            "metadata": {"locale": "xx_XX"},
+            "schema": {"name": {"fake": ["name"]}}
+        }"#;
+        assert!(matches!(
+            ConfigParser::parse(unknown_default).unwrap_err().issues()[0].error,
+            DataFakeError::InvalidLocale(_)
+        ));
+
+        let unknown_argument = r#"{
+            "schema": {"name": {"fake": ["city", "klingon"]}}
+        }"#;
+        assert!(matches!(
+            ConfigParser::parse(unknown_argument).unwrap_err().issues()[0].error,
+            DataFakeError::InvalidLocale(_)
+        ));
+    }
+
+    #[test]
+    fn test_method_signature_validation() {
+        let parse = |fake: Value| {
+            ConfigParser::parse_value(serde_json::json!({"schema": {"field": {"fake": fake}}}))
+        };
+
+        let err = parse(serde_json::json!(["emial"])).unwrap_err();
+        assert!(
+            err.to_string().contains("Unknown fake method 'emial'"),
+            "{err}"
+        );
+        // Nested calls are checked too
+        assert!(
+            ConfigParser::parse(r#"{"schema": {"x": {"cat": ["a", {"fake": ["nmae"]}]}}}"#)
+                .is_err()
+        );
+
+        assert!(parse(serde_json::json!(["uuid", 4])).is_err());
+        assert!(parse(serde_json::json!(["regex"])).is_err());
+        assert!(parse(serde_json::json!(["password", "long"])).is_err());
+        assert!(parse(serde_json::json!(["sentence", 3.5])).is_err());
+        assert!(parse(serde_json::json!(["sentence", 5, 5])).is_err());
+        assert!(parse(serde_json::json!(["sentence", 12])).is_err());
+        assert!(parse(serde_json::json!(["alphanumeric", 5, 5])).is_ok());
+        assert!(parse(serde_json::json!(["password", 10, 20])).is_ok());
+        assert!(parse(serde_json::json!(["datetime", null, "now", "unix"])).is_ok());
+    }
+
+    #[test]
+    fn test_error_locations_and_suggestions() {
+        let config_json = r#"{
+            "metadata": {"locale": "en_US"},
+            "variables": {"userId": {"fake": ["uuid"]}},
+            "schema": {
+                "user": {
+                    "id": {"var": "userID"},
+                    "profile": {"email": {"fake": ["emial"]}},
+                    "tags": {"repeat": [2, {"fake": ["word", 3]}]},
+                    "a/b": null
+                }
+            }
+        }"#;
+
+        let err = ConfigParser::parse(config_json).unwrap_err();
+        let issues = err.issues();
+        assert_eq!(issues.len(), 3, "{err}");
+
+        let at = |path: &str| issues.iter().find(|issue| issue.path == path).unwrap();
+        let email = at("/schema/user/profile/email");
+        assert_eq!(email.suggestion.as_deref(), Some("email"));
+        assert_eq!(email.snippet.as_deref(), Some(r#"{"fake":["emial"]}"#));
+        assert!(matches!(
+            at("/schema/user/tags/repeat/1").error,
+            DataFakeError::InvalidConfig(_)
+        ));
+        assert!(at("/schema/user/a~1b").snippet.is_none());
+        assert!(err.to_string().starts_with("3 problems in configuration:"));
+
+        // Unknown variables evaluate to null, so they are only warnings
+        let unknown = r#"{"variables": {"userId": 1}, "schema": {"id": {"var": "userID"}}}"#;
+        let config = ConfigParser::parse(unknown).unwrap();
+        let warnings = ConfigParser::warnings(&config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, "/schema/id");
+        assert_eq!(warnings[0].suggestion.as_deref(), Some("userId"));
+
+        // Inside iterating operators, `var` reads the current element
+        let iterating = r#"{"schema": {"total": {"reduce": [[1, 2], {"+": [{"var": "current"}, {"var": "accumulator"}]}, 0]}}}"#;
+        let config = ConfigParser::parse(iterating).unwrap();
+        assert!(ConfigParser::warnings(&config).is_empty());
+        let initial = r#"{"schema": {"total": {"reduce": [[1, 2], {"var": "current"}, {"var": "accumulator"}]}}}"#;
+        let config = ConfigParser::parse(initial).unwrap();
+        let warnings = ConfigParser::warnings(&config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, "/schema/total/reduce/2");
+        let config = ConfigParser::parse(r#"{"schema": {"n": {"var": "$index"}}}"#).unwrap();
+        assert!(ConfigParser::warnings(&config).is_empty());
+        assert!(ConfigParser::parse(r#"{"schema": {"n": {"var": ""}}}"#).is_err());
+    }
+
+    #[test]
+    fn test_regex_validation() {
+        let valid = r#"{"schema": {"code": {"fake": ["regex", "[A-Z]{3}-\\d+"]}}}"#;
+        assert!(ConfigParser::parse(valid).is_ok());
+
+        let backreference = r#"{"schema": {"code": {"fake": ["regex", "(a)\\1"]}}}"#;
+        assert!(matches!(
+            ConfigParser::parse(backreference).unwrap_err().issues()[0].error,
+            DataFakeError::InvalidRegex(_)
+        ));
+    }
+
+    #[test]
+    fn test_iban_country_validation() {
+        let valid = r#"{"schema": {"iban": {"fake": ["iban", "gb"]}}}"#;
+        assert!(ConfigParser::parse(valid).is_ok());
+
+        let unknown = r#"{"schema": {"iban": {"fake": ["iban", "ZZ"]}}}"#;
+        assert!(ConfigParser::parse(unknown).is_err());
+    }
+
+    #[test]
+    fn test_datetime_validation() {
+        let valid =
+            r#"{"schema": {"at": {"fake": ["datetime", "-30d", "now", "unix", "Europe/Paris"]}}}"#;
+        assert!(ConfigParser::parse(valid).is_ok());
+
+        let reversed = r#"{"schema": {"at": {"fake": ["date", "2024-12-31", "2024-01-01"]}}}"#;
+        assert!(ConfigParser::parse(reversed).is_err());
+
+        let bad_format = r#"{"schema": {"at": {"fake": ["datetime", null, null, "%Q"]}}}"#;
+        assert!(ConfigParser::parse(bad_format).is_err());
+    }
 }
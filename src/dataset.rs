@@ -0,0 +1,419 @@
+use crate::config::ConfigParser;
+use crate::error::{DataFakeError, Result};
+use crate::generator::DataGenerator;
+use crate::output::RecordWriter;
+use crate::plan::PlanNode;
+use crate::rng;
+use crate::types::{DataFakeConfig, DatasetConfig, EntityConfig, Metadata};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+/// Generates linked records for every entity of a [`DatasetConfig`].
+///
+/// Entities are generated in dependency order, so when `orders` has
+/// `{"ref": "users"}` fields, all users are generated first and each order
+/// references the primary key of one of them.
+pub struct DatasetGenerator {
+    config: DatasetConfig,
+    /// In dependency order
+    entities: Vec<Entity>,
+}
+
+struct Entity {
+    name: String,
+    count: usize,
+    primary_key: String,
+    /// Whether another entity references this one, so its keys are collected
+    referenced: bool,
+    /// The schema with each `ref` replaced by the referenced primary key's
+    /// template, so writers infer columns and types of the keys it produces
+    output_schema: Value,
+    generator: DataGenerator,
+}
+
+impl DatasetGenerator {
+    pub fn new(config: DatasetConfig) -> Result<Self> {
+        let order = entity_order(&config)?;
+        let referenced: BTreeSet<&str> = config.entities.values().flat_map(references).collect();
+
+        let entities = order
+            .into_iter()
+            .map(|name| {
+                let entity = &config.entities[name];
+                Ok(Entity {
+                    name: name.to_string(),
+                    count: entity.count,
+                    primary_key: entity.primary_key.clone(),
+                    referenced: referenced.contains(name),
+                    output_schema: resolve_references(&entity.schema, &config),
+                    generator: DataGenerator::new(entity_config(&config, name, entity))?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { config, entities })
+    }
+
+    /// Seeds every entity, overriding any `metadata.seed` from the config. Each
+    /// entity draws from its own stream derived from `seed` and its name.
+    pub fn with_seed(self, seed: u64) -> Self {
+        let entities = self
+            .entities
+            .into_iter()
+            .map(|entity| Entity {
+                generator: entity
+                    .generator
+                    .with_seed(rng::entity_seed(seed, &entity.name)),
+                ..entity
+            })
+            .collect();
+        Self { entities, ..self }
+    }
+
+    /// Generates each entity on `threads` threads (`0` uses all available cores).
+    pub fn with_threads(self, threads: usize) -> Self {
+        let entities = self
+            .entities
+            .into_iter()
+            .map(|entity| Entity {
+                generator: entity.generator.with_threads(threads),
+                ..entity
+            })
+            .collect();
+        Self { entities, ..self }
+    }
+
+    pub fn from_json(json_str: &str) -> Result<Self> {
+        let config = ConfigParser::parse_dataset(json_str)?;
+        Self::new(config)
+    }
+
+    pub fn from_value(json_value: Value) -> Result<Self> {
+        let config = ConfigParser::parse_dataset_value(json_value)?;
+        Self::new(config)
+    }
+
+    pub fn config(&self) -> &DatasetConfig {
+        &self.config
+    }
+
+    /// Entity names in the order they are generated, referenced entities first.
+    pub fn entity_names(&self) -> impl Iterator<Item = &str> {
+        self.entities.iter().map(|entity| entity.name.as_str())
+    }
+
+    /// Generates every entity's records, in dependency order.
+    ///
+    /// Like [`DataGenerator::generate_batch`], calling this again continues each
+    /// entity's records (and sequences) where the previous call stopped; refs
+    /// then point at the newly generated keys.
+    pub fn generate(&self) -> Result<Vec<(String, Vec<Value>)>> {
+        self.entities
+            .iter()
+            .map(|entity| {
+                let records = entity.generator.generate_batch(entity.count)?;
+                if entity.referenced {
+                    let keys = records
+                        .iter()
+                        .map(|record| entity.key(record))
+                        .collect::<Result<Vec<_>>>()?;
+                    self.bind(entity, keys);
+                }
+                Ok((entity.name.clone(), records))
+            })
+            .collect()
+    }
+
+    /// Streams every entity's records to its own writer, in dependency order.
+    ///
+    /// `sink` is called with each entity's name and schema to create the writer
+    /// for that entity. Only the primary keys of referenced entities are held in
+    /// memory.
+    pub fn write_to<W, F>(&self, mut sink: F) -> Result<()>
+    where
+        W: RecordWriter,
+        F: FnMut(&str, &Value) -> Result<W>,
+    {
+        for entity in &self.entities {
+            let mut writer = sink(&entity.name, &entity.output_schema)?;
+            let mut keys = Vec::new();
+            for record in entity.generator.stream(entity.count) {
+                let record = record?;
+                if entity.referenced {
+                    keys.push(entity.key(&record)?);
+                }
+                writer.write_record(&record)?;
+            }
+            writer.finish()?;
+            if entity.referenced {
+                self.bind(entity, keys);
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes `keys` the values that refs to `entity` pick from.
+    fn bind(&self, entity: &Entity, keys: Vec<Value>) {
+        let keys = Arc::new(keys);
+        for other in &self.entities {
+            other.generator.bind_references(&entity.name, &keys);
+        }
+    }
+}
+
+impl Entity {
+    fn key(&self, record: &Value) -> Result<Value> {
+        record.get(&self.primary_key).cloned().ok_or_else(|| {
+            DataFakeError::InvalidConfig(format!(
+                "Entity '{}' record has no primary key field '{}'",
+                self.name, self.primary_key
+            ))
+        })
+    }
+}
+
+/// The single-schema config for entity `name`, sharing the dataset's metadata.
+/// A dataset seed is turned into a seed of the entity's own.
+pub(crate) fn entity_config(
+    config: &DatasetConfig,
+    name: &str,
+    entity: &EntityConfig,
+) -> DataFakeConfig {
+    let metadata = config.metadata.as_ref().map(|metadata| Metadata {
+        seed: metadata.seed.map(|seed| rng::entity_seed(seed, name)),
+        ..metadata.clone()
+    });
+    DataFakeConfig {
+        metadata,
+        variables: entity.variables.clone(),
+        schema: entity.schema.clone(),
+    }
+}
+
+/// Replaces every `ref` in `schema` with the template of the referenced
+/// entity's primary key, itself resolved.
+fn resolve_references(schema: &Value, config: &DatasetConfig) -> Value {
+    match schema {
+        Value::Object(obj) => {
+            if obj.len() == 1
+                && let Some(args) = obj.get("ref")
+                && let Ok((target, _)) = PlanNode::reference_args(args)
+                && let Some(entity) = config.entities.get(target)
+                && let Some(key) = entity.schema.get(&entity.primary_key)
+            {
+                return resolve_references(key, config);
+            }
+            Value::Object(
+                obj.iter()
+                    .map(|(key, value)| (key.clone(), resolve_references(value, config)))
+                    .collect(),
+            )
+        }
+        Value::Array(arr) => Value::Array(
+            arr.iter()
+                .map(|value| resolve_references(value, config))
+                .collect(),
+        ),
+        _ => schema.clone(),
+    }
+}
+
+/// Entities referenced by `ref`s in an entity's schema or variables.
+pub(crate) fn references(entity: &EntityConfig) -> BTreeSet<&str> {
+    let mut entities = BTreeSet::new();
+    PlanNode::collect_references(&entity.schema, &mut entities);
+    for expression in entity.variables.values() {
+        PlanNode::collect_references(expression, &mut entities);
+    }
+    entities
+}
+
+/// Entity names ordered so that every entity comes after those it references.
+pub(crate) fn entity_order(config: &DatasetConfig) -> Result<Vec<&str>> {
+    let mut dependencies = BTreeMap::new();
+    for (name, entity) in &config.entities {
+        let references = references(entity);
+        if let Some(unknown) = references
+            .iter()
+            .find(|target| !config.entities.contains_key(**target))
+        {
+            return Err(DataFakeError::InvalidConfig(format!(
+                "Entity '{name}' references unknown entity '{unknown}'"
+            )));
+        }
+        dependencies.insert(name.as_str(), references);
+    }
+
+    let mut ordered = Vec::with_capacity(dependencies.len());
+    let mut done = BTreeSet::new();
+    let mut path = Vec::new();
+    for name in dependencies.keys() {
+        visit_entity(name, &dependencies, &mut done, &mut path, &mut ordered)?;
+    }
+    Ok(ordered)
+}
+
+fn visit_entity<'a>(
+    name: &'a str,
+    dependencies: &BTreeMap<&'a str, BTreeSet<&'a str>>,
+    done: &mut BTreeSet<&'a str>,
+    path: &mut Vec<&'a str>,
+    ordered: &mut Vec<&'a str>,
+) -> Result<()> {
+    if done.contains(name) {
+        return Ok(());
+    }
+    if let Some(start) = path.iter().position(|visiting| *visiting == name) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(name);
+        return Err(DataFakeError::CircularEntityReference(cycle.join(" -> ")));
+    }
+
+    path.push(name);
+    for dependency in &dependencies[name] {
+        visit_entity(dependency, dependencies, done, path, ordered)?;
+    }
+    path.pop();
+
+    done.insert(name);
+    ordered.push(name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::NdjsonWriter;
+    use serde_json::json;
+    use std::collections::HashSet;
+
+    fn shop() -> Value {
+        json!({
+            "metadata": {"seed": 42},
+            "entities": {
+                "order_items": {
+                    "count": 60,
+                    "schema": {
+                        "id": {"sequence": {}},
+                        "order_id": {"ref": "orders"},
+                        "quantity": {"fake": ["u8", 1, 5]}
+                    }
+                },
+                "orders": {
+                    "count": 20,
+                    "schema": {
+                        "id": {"sequence": {"start": 1000}},
+                        "user_id": {"ref": ["users", "round_robin"]}
+                    }
+                },
+                "users": {
+                    "count": 5,
+                    "primary_key": "email",
+                    "schema": {"email": {"unique": {"fake": ["email"]}}}
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_generate_linked_entities() {
+        let generator = DatasetGenerator::from_value(shop()).unwrap();
+        assert_eq!(
+            generator.entity_names().collect::<Vec<_>>(),
+            ["users", "orders", "order_items"]
+        );
+
+        let dataset = generator.generate().unwrap();
+        let records: BTreeMap<&str, &Vec<Value>> = dataset
+            .iter()
+            .map(|(name, records)| (name.as_str(), records))
+            .collect();
+        assert_eq!(records["users"].len(), 5);
+        assert_eq!(records["orders"].len(), 20);
+        assert_eq!(records["order_items"].len(), 60);
+
+        // Round-robin uses every user evenly, in order
+        for (i, order) in records["orders"].iter().enumerate() {
+            assert_eq!(order["user_id"], records["users"][i % 5]["email"]);
+        }
+        let order_ids: HashSet<&Value> = records["orders"].iter().map(|o| &o["id"]).collect();
+        assert!(
+            records["order_items"]
+                .iter()
+                .all(|item| order_ids.contains(&item["order_id"]))
+        );
+
+        // Seeded datasets are reproducible, including in parallel
+        let again = DatasetGenerator::from_value(shop())
+            .unwrap()
+            .with_threads(3)
+            .generate()
+            .unwrap();
+        assert_eq!(dataset, again);
+        let other = DatasetGenerator::from_value(shop())
+            .unwrap()
+            .with_seed(7)
+            .generate()
+            .unwrap();
+        assert_ne!(dataset, other);
+    }
+
+    #[test]
+    fn test_write_to_per_entity() {
+        let generator = DatasetGenerator::from_value(shop()).unwrap();
+        let mut written = Vec::new();
+        generator
+            .write_to(|name, schema| {
+                if name == "orders" {
+                    // The ref is described by the referenced key's template
+                    assert_eq!(schema["user_id"], json!({"unique": {"fake": ["email"]}}));
+                }
+                written.push(name.to_string());
+                Ok(NdjsonWriter::new(std::io::sink()))
+            })
+            .unwrap();
+        assert_eq!(written, ["users", "orders", "order_items"]);
+    }
+
+    #[test]
+    fn test_invalid_datasets() {
+        let config = |entities: Value| DatasetGenerator::from_value(json!({"entities": entities}));
+
+        let err = config(json!({
+            "a": {"count": 1, "schema": {"id": 1, "b": {"ref": "b"}}},
+            "b": {"count": 1, "schema": {"id": 1, "a": {"ref": "a"}}}
+        }))
+        .err()
+        .unwrap();
+        assert!(matches!(
+            &err.issues()[0].error,
+            DataFakeError::CircularEntityReference(cycle) if cycle == "a -> b -> a"
+        ));
+        assert!(
+            config(json!({"orders": {"count": 1, "schema": {"user": {"ref": "users"}}}})).is_err()
+        );
+        assert!(
+            config(json!({
+                "users": {"count": 1, "schema": {"email": "x"}},
+                "orders": {"count": 1, "schema": {"user": {"ref": "users"}}}
+            }))
+            .is_err()
+        );
+        assert!(
+            config(json!({
+                "users": {"count": 1, "schema": {"id": 1}},
+                "orders": {"count": 1, "schema": {"user": {"ref": ["users", "first"]}}}
+            }))
+            .is_err()
+        );
+        assert!(config(json!({})).is_err());
+
+        // A parent with no records leaves nothing to reference
+        let empty = config(json!({
+            "users": {"count": 0, "schema": {"id": {"sequence": {}}}},
+            "orders": {"count": 1, "schema": {"user": {"ref": "users"}}}
+        }))
+        .unwrap();
+        assert!(empty.generate().is_err());
+    }
+}
@@ -1,29 +1,25 @@
 use crate::error::{DataFakeError, Result};
 use crate::operators::FakeOperator;
+use crate::plan::PlanNode;
 use crate::types::GenerationContext;
-use datalogic_rs::DataLogic;
+use datalogic_rs::{CompiledLogic, DataLogic};
 use serde_json::{Map, Value};
-use std::cell::RefCell;
-
-thread_local! {
-    static THREAD_LOCAL_DATA_LOGIC: RefCell<Option<DataLogic>> = const { RefCell::new(None) };
-}
-
-fn get_or_init_datalogic() -> &'static RefCell<Option<DataLogic>> {
-    THREAD_LOCAL_DATA_LOGIC.with(|dl_cell| {
-        let mut dl_opt = dl_cell.borrow_mut();
-        if dl_opt.is_none() {
-            // Note: Cannot use preserve_structure mode with custom operators in v4
-            // This is a limitation in datalogic-rs v4 where custom operators are not
-            // recognized in preserve_structure mode
-            let mut dl = DataLogic::new();
-            // Register the fake operator
-            dl.add_operator("fake".to_string(), Box::new(FakeOperator));
-
-            *dl_opt = Some(dl);
-        }
-        // This is safe because we're returning a reference to a thread_local
-        unsafe { &*(dl_cell as *const RefCell<Option<DataLogic>>) }
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, OnceLock};
+
+/// Shared JSONLogic engine with the fake operator registered. `DataLogic` is
+/// `Send + Sync`, so one instance serves every thread.
+static DATA_LOGIC: OnceLock<DataLogic> = OnceLock::new();
+
+fn datalogic() -> &'static DataLogic {
+    DATA_LOGIC.get_or_init(|| {
+        // Note: Cannot use preserve_structure mode with custom operators in v4
+        // This is a limitation in datalogic-rs v4 where custom operators are not
+        // recognized in preserve_structure mode
+        let mut dl = DataLogic::new();
+        // Register the fake operator
+        dl.add_operator("fake".to_string(), Box::new(FakeOperator));
+        dl
     })
 }
 
@@ -31,70 +27,40 @@ pub struct Engine;
 
 impl Engine {
     pub fn evaluate(expression: &Value, context: &GenerationContext) -> Result<Value> {
-        // Evaluate the expression directly with JSONLogic (fake operator is registered)
-        let dl_cell = get_or_init_datalogic();
-        let dl_opt = dl_cell.borrow();
-        let data_logic = dl_opt.as_ref().unwrap();
+        let compiled = Self::compile(expression)?;
 
         // Convert context to JSON value for datalogic
         let context_json =
             serde_json::to_value(&context.variables).map_err(DataFakeError::JsonError)?;
 
-        // Compile and evaluate the expression
-        let compiled = data_logic.compile(expression).map_err(|e| {
+        Self::evaluate_compiled(&compiled, Arc::new(context_json))
+    }
+
+    /// Compiles a JSONLogic expression with the fake operator registered.
+    pub(crate) fn compile(expression: &Value) -> Result<Arc<CompiledLogic>> {
+        datalogic().compile(expression).map_err(|e| {
             DataFakeError::FakeOperatorError(format!("JSONLogic compilation error: {e}"))
-        })?;
+        })
+    }
 
-        data_logic
-            .evaluate_owned(&compiled, context_json)
-            .map_err(|e| {
-                DataFakeError::FakeOperatorError(format!("JSONLogic evaluation error: {e}"))
-            })
+    pub(crate) fn evaluate_compiled(compiled: &CompiledLogic, data: Arc<Value>) -> Result<Value> {
+        datalogic().evaluate(compiled, data).map_err(|e| {
+            DataFakeError::FakeOperatorError(format!("JSONLogic evaluation error: {e}"))
+        })
     }
 
     pub fn process_schema(schema: &Value, context: &GenerationContext) -> Result<Value> {
         // Since we can't use preserve_structure with custom operators in v4,
-        // we need to manually handle object structure preservation
-        match schema {
-            Value::Object(obj) if obj.len() == 1 => {
-                // Single-key objects might be JSONLogic operators
-                if let Some((key, _value)) = obj.iter().next() {
-                    // Check if this looks like a JSONLogic operator
-                    // Known operators or custom operators should be evaluated
-                    if Self::is_jsonlogic_operator(key) {
-                        return Self::evaluate(schema, context);
-                    }
-                }
-                // Not an operator, process as regular object
-                let mut result = serde_json::Map::new();
-                for (key, value) in obj {
-                    result.insert(key.clone(), Self::process_schema(value, context)?);
-                }
-                Ok(Value::Object(result))
-            }
-            Value::Object(obj) => {
-                // Multi-key objects are treated as templates
-                let mut result = serde_json::Map::new();
-                for (key, value) in obj {
-                    result.insert(key.clone(), Self::process_schema(value, context)?);
-                }
-                Ok(Value::Object(result))
-            }
-            Value::Array(arr) => {
-                let mut result = Vec::new();
-                for item in arr {
-                    result.push(Self::process_schema(item, context)?);
-                }
-                Ok(Value::Array(result))
-            }
-            _ => {
-                // Primitive values are returned as-is
-                Ok(schema.clone())
-            }
-        }
+        // the plan handles object structure preservation itself
+        let context_json =
+            serde_json::to_value(&context.variables).map_err(DataFakeError::JsonError)?;
+
+        PlanNode::compile(schema)?.execute(&Arc::new(context_json))
     }
 
-    fn is_jsonlogic_operator(key: &str) -> bool {
+    /// Whether a single-key object with this key is a JSONLogic operator call,
+    /// including the `fake` operator.
+    pub fn is_jsonlogic_operator(key: &str) -> bool {
         // Check if this is a known JSONLogic operator or our custom operator
         matches!(
             key,
@@ -108,20 +74,105 @@ impl Engine {
     }
 
     pub fn generate_variables(variables: &Map<String, Value>) -> Result<Map<String, Value>> {
-        if variables.is_empty() {
-            return Ok(Map::new());
+        // Variables are evaluated in dependency order so each one can reference
+        // the variables generated before it
+        let mut context = GenerationContext::new();
+        for (name, expression) in Self::sort_variables(variables)? {
+            let value = Self::process_schema(expression, &context)?;
+            context.set_variable(name.clone(), value);
+        }
+
+        Ok(context.variables.into_iter().collect())
+    }
+
+    /// Orders variables so that every variable comes after the variables it
+    /// references through `var`. Ties are broken by name to keep seeded runs stable.
+    pub(crate) fn sort_variables<'a, I>(variables: I) -> Result<Vec<(&'a String, &'a Value)>>
+    where
+        I: IntoIterator<Item = (&'a String, &'a Value)>,
+    {
+        let variables: BTreeMap<&String, &Value> = variables.into_iter().collect();
+        let dependencies: BTreeMap<&String, BTreeSet<&String>> = variables
+            .iter()
+            .map(|(name, expression)| {
+                let mut references = BTreeSet::new();
+                Self::collect_var_references(expression, &mut references);
+                let known = variables
+                    .keys()
+                    .filter(|candidate| references.contains(candidate.as_str()))
+                    .copied()
+                    .collect();
+                (*name, known)
+            })
+            .collect();
+
+        let mut ordered = Vec::with_capacity(variables.len());
+        let mut done = BTreeSet::new();
+        let mut path = Vec::new();
+        for name in variables.keys() {
+            Self::visit_variable(name, &dependencies, &mut done, &mut path, &mut ordered)?;
         }
 
-        // Since we can't use preserve_structure with custom operators,
-        // we process each variable individually
-        let temp_context = GenerationContext::new();
-        let variables_as_value = Value::Object(variables.clone());
+        Ok(ordered
+            .into_iter()
+            .map(|name| (name, variables[name]))
+            .collect())
+    }
+
+    fn visit_variable<'a>(
+        name: &'a String,
+        dependencies: &BTreeMap<&'a String, BTreeSet<&'a String>>,
+        done: &mut BTreeSet<&'a String>,
+        path: &mut Vec<&'a String>,
+        ordered: &mut Vec<&'a String>,
+    ) -> Result<()> {
+        if done.contains(name) {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|visiting| *visiting == name) {
+            let cycle: Vec<&str> = path[start..]
+                .iter()
+                .map(|n| n.as_str())
+                .chain(std::iter::once(name.as_str()))
+                .collect();
+            return Err(DataFakeError::CircularVariableReference(cycle.join(" -> ")));
+        }
 
-        match Self::process_schema(&variables_as_value, &temp_context)? {
-            Value::Object(map) => Ok(map),
-            _ => Err(DataFakeError::FakeOperatorError(
-                "Variables evaluation did not return an object".to_string(),
-            )),
+        path.push(name);
+        for dependency in &dependencies[name] {
+            Self::visit_variable(dependency, dependencies, done, path, ordered)?;
+        }
+        path.pop();
+
+        done.insert(name);
+        ordered.push(name);
+        Ok(())
+    }
+
+    /// Collects the root names referenced by `var` operators in an expression
+    /// (`"user.name"` references `user`).
+    fn collect_var_references<'a>(expression: &'a Value, references: &mut BTreeSet<&'a str>) {
+        match expression {
+            Value::Object(obj) => {
+                if let Some(target) = obj.get("var") {
+                    let path = match target {
+                        Value::Array(arr) => arr.first(),
+                        other => Some(other),
+                    };
+                    if let Some(Value::String(path)) = path {
+                        references.insert(path.split('.').next().unwrap_or(path));
+                    }
+                }
+                for value in obj.values() {
+                    Self::collect_var_references(value, references);
+                }
+            }
+            Value::Array(arr) => {
+                for item in arr {
+                    Self::collect_var_references(item, references);
+                }
+            }
+            _ => {}
         }
     }
 }
@@ -185,6 +236,22 @@ mod tests {
         assert_eq!(result["tags"].as_array().unwrap().len(), 3);
     }
 
+    #[test]
+    fn test_process_schema_omits_optional_keys() {
+        let schema = json!({
+            "id": {"fake": ["uuid"]},
+            "deletedAt": {"optional": [1, {"fake": ["datetime"]}]},
+            "phone": {"nullable": [1, {"fake": ["phone_number"]}]}
+        });
+
+        let context = GenerationContext::new();
+        let result = Engine::process_schema(&schema, &context).unwrap();
+
+        let fields = result.as_object().unwrap();
+        assert!(!fields.contains_key("deletedAt"));
+        assert_eq!(fields["phone"], Value::Null);
+    }
+
     #[test]
     fn test_generate_variables() {
         let variables = json!({
@@ -203,6 +270,46 @@ mod tests {
         assert!(result["timestamp"].is_number());
     }
 
+    #[test]
+    fn test_generate_variables_referencing_variables() {
+        let variables = json!({
+            "email": {"cat": [{"var": "firstName"}, ".", {"var": "lastName"}, "@example.com"]},
+            "firstName": {"fake": ["first_name"]},
+            "lastName": {"fake": ["last_name"]},
+            "greeting": {"cat": ["Hello ", {"var": "email"}]}
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+
+        let result = Engine::generate_variables(&variables).unwrap();
+
+        let expected_email = format!(
+            "{}.{}@example.com",
+            result["firstName"].as_str().unwrap(),
+            result["lastName"].as_str().unwrap()
+        );
+        assert_eq!(result["email"], expected_email);
+        assert_eq!(result["greeting"], format!("Hello {expected_email}"));
+    }
+
+    #[test]
+    fn test_sort_variables_detects_self_reference() {
+        let variables = json!({
+            "ok": {"fake": ["uuid"]},
+            "loop": {"if": [{"var": "loop.nested"}, 1, 2]}
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+
+        let result = Engine::sort_variables(&variables);
+        assert!(matches!(
+            result,
+            Err(DataFakeError::CircularVariableReference(ref cycle)) if cycle == "loop -> loop"
+        ));
+    }
+
     #[test]
     fn test_process_schema_with_cat_operator() {
         let schema = json!({
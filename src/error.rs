@@ -1,3 +1,4 @@
+use std::fmt;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -11,9 +12,18 @@ pub enum DataFakeError {
     #[error("Variable not found: {0}")]
     VariableNotFound(String),
 
+    #[error("Circular variable reference: {0}")]
+    CircularVariableReference(String),
+
+    #[error("Circular reference between entities: {0}")]
+    CircularEntityReference(String),
+
     #[error("JSON serialization error: {0}")]
     JsonError(#[from] serde_json::Error),
 
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     #[error("Fake operator error: {0}")]
     FakeOperatorError(String),
 
@@ -23,8 +33,72 @@ pub enum DataFakeError {
     #[error("Invalid locale: {0}")]
     InvalidLocale(String),
 
+    #[error("Invalid regex pattern: {0}")]
+    InvalidRegex(String),
+
+    #[error("Could not generate a unique value for {template} after {attempts} attempts")]
+    UniqueValuesExhausted { template: String, attempts: usize },
+
     #[error("Invalid numeric range: min={min}, max={max}")]
     InvalidRange { min: f64, max: f64 },
+
+    /// Every problem found while validating a config, each with its location
+    #[error("{}", describe_issues(.0))]
+    Validation(Vec<ConfigIssue>),
+}
+
+impl DataFakeError {
+    /// The located problems of a [`DataFakeError::Validation`] error, or none for
+    /// other errors.
+    pub fn issues(&self) -> &[ConfigIssue] {
+        match self {
+            DataFakeError::Validation(issues) => issues,
+            _ => &[],
+        }
+    }
+}
+
+/// A problem at one place in a config.
+#[derive(Debug)]
+pub struct ConfigIssue {
+    /// JSON pointer to the offending value, e.g. `/schema/user/profile/email`
+    pub path: String,
+    /// Compact JSON of the offending value, shortened when long
+    pub snippet: Option<String>,
+    /// The name probably meant, for misspelled fake methods and variables
+    pub suggestion: Option<String>,
+    pub error: DataFakeError,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{path}: {}", self.error)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean '{suggestion}'?)")?;
+        }
+        if let Some(snippet) = &self.snippet {
+            write!(f, " in {snippet}")?;
+        }
+        Ok(())
+    }
+}
+
+fn describe_issues(issues: &[ConfigIssue]) -> String {
+    match issues {
+        [issue] => format!("Invalid configuration at {issue}"),
+        issues => {
+            let mut description = format!("{} problems in configuration:", issues.len());
+            for issue in issues {
+                description.push_str(&format!("\n  {issue}"));
+            }
+            description
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, DataFakeError>;
@@ -0,0 +1,496 @@
+use crate::operators::FakeOperator;
+use crate::operators::datetime;
+use crate::operators::fake::{MONTH_NAMES, NUMERIC_METHODS};
+use crate::plan::{INDEX_VARIABLE, PlanNode, RECORD_VARIABLE, Sequence};
+use crate::types::DataFakeConfig;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+
+/// The JSON Schema dialect of exported schemas.
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Infers a JSON Schema describing the records `config` generates.
+///
+/// Fake methods map to a type and, where the output has one, a `format` or
+/// `pattern`. Numeric methods carry their `minimum`/`maximum`, and `choice`,
+/// `weighted` and `one_of` of literals become an `enum`. Object keys are
+/// `required` unless wrapped in `optional`, `nullable` adds `null` to the type,
+/// and `repeat` bounds become `minItems`/`maxItems`. Variables are described by
+/// their own templates. JSONLogic expressions are typed by their operator only.
+pub fn export_json_schema(config: &DataFakeConfig) -> Value {
+    let mut exporter = Exporter {
+        variables: &config.variables,
+        resolving: Vec::new(),
+    };
+
+    let mut root = Map::new();
+    root.insert("$schema".to_string(), json!(JSON_SCHEMA_DIALECT));
+    if let Some(metadata) = &config.metadata {
+        if let Some(name) = &metadata.name {
+            root.insert("title".to_string(), json!(name));
+        }
+        if let Some(description) = &metadata.description {
+            root.insert("description".to_string(), json!(description));
+        }
+    }
+    if let Value::Object(schema) = exporter.schema(&config.schema) {
+        root.extend(schema);
+    }
+    Value::Object(root)
+}
+
+struct Exporter<'a> {
+    variables: &'a HashMap<String, Value>,
+    /// Variables being described, to stop at cycles
+    resolving: Vec<&'a str>,
+}
+
+impl<'a> Exporter<'a> {
+    fn schema(&mut self, template: &'a Value) -> Value {
+        match template {
+            Value::Object(obj) if obj.len() == 1 => {
+                let (key, args) = obj.iter().next().unwrap();
+                if PlanNode::is_expression_key(key) {
+                    self.expression(key, args)
+                } else {
+                    self.object(obj)
+                }
+            }
+            Value::Object(obj) => self.object(obj),
+            Value::Array(items) => self.tuple(items),
+            literal => json!({"const": literal}),
+        }
+    }
+
+    fn object(&mut self, obj: &'a Map<String, Value>) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for (key, template) in obj {
+            let template = match optional_template(template) {
+                Some(item) => item,
+                None => {
+                    required.push(json!(key));
+                    template
+                }
+            };
+            properties.insert(key.clone(), self.schema(template));
+        }
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false
+        })
+    }
+
+    fn tuple(&mut self, items: &'a [Value]) -> Value {
+        let min_items = items
+            .iter()
+            .filter(|item| optional_template(item).is_none())
+            .count();
+        let prefix_items: Vec<Value> = items
+            .iter()
+            .map(|item| self.schema(optional_template(item).unwrap_or(item)))
+            .collect();
+        json!({
+            "type": "array",
+            "prefixItems": prefix_items,
+            "items": false,
+            "minItems": min_items,
+            "maxItems": items.len()
+        })
+    }
+
+    fn expression(&mut self, key: &str, args: &'a Value) -> Value {
+        match key {
+            "fake" => match args {
+                Value::Array(args) => fake(args),
+                method => fake(std::slice::from_ref(method)),
+            },
+            "repeat" => {
+                let Ok((count, item)) = PlanNode::repeat_args(args) else {
+                    return json!({"type": "array"});
+                };
+                let mut schema = json!({"type": "array", "items": self.schema(item)});
+                if let Ok((min, max)) = PlanNode::repeat_bounds(count) {
+                    schema["minItems"] = json!(min);
+                    schema["maxItems"] = json!(max);
+                }
+                schema
+            }
+            "unique" => match PlanNode::unique_args(args) {
+                Ok((item, _)) => self.schema(item),
+                Err(_) => json!({}),
+            },
+            "optional" => match PlanNode::probability_args(key, args) {
+                Ok((_, item)) => self.schema(item),
+                Err(_) => json!({}),
+            },
+            "nullable" => match PlanNode::probability_args(key, args) {
+                Ok((probability, _)) if probability >= 1.0 => json!({"type": "null"}),
+                Ok((probability, item)) if probability <= 0.0 => self.schema(item),
+                Ok((_, item)) => nullable(self.schema(item)),
+                Err(_) => json!({}),
+            },
+            "one_of" => match PlanNode::one_of_args(args) {
+                Ok(items) => {
+                    let schemas = items.iter().map(|item| self.schema(item)).collect();
+                    any_of(schemas)
+                }
+                Err(_) => json!({}),
+            },
+            "sequence" => match Sequence::parse(args) {
+                Ok(sequence) => sequence_schema(&sequence),
+                Err(_) => json!({}),
+            },
+            "ref" => match PlanNode::reference_args(args) {
+                Ok((entity, _)) => json!({"description": format!("A primary key of {entity}")}),
+                Err(_) => json!({}),
+            },
+            "var" => self.variable(args),
+            "if" | "?:" => match args {
+                Value::Array(args) if args.len() >= 2 => {
+                    // [condition, value, condition, value, ..., else]
+                    let mut values: Vec<&Value> = args.iter().skip(1).step_by(2).collect();
+                    if args.len() % 2 == 1 {
+                        values.push(&args[args.len() - 1]);
+                    }
+                    let schemas = values.into_iter().map(|value| self.schema(value)).collect();
+                    any_of(schemas)
+                }
+                _ => json!({}),
+            },
+            "cat" | "substr" => json!({"type": "string"}),
+            "+" | "-" | "*" | "/" | "%" | "max" | "min" => json!({"type": "number"}),
+            "==" | "!=" | "===" | "!==" | "!" | "!!" | ">" | ">=" | "<" | "<=" | "in" | "all"
+            | "none" | "some" => json!({"type": "boolean"}),
+            "map" | "filter" | "merge" | "missing" | "missing_some" => json!({"type": "array"}),
+            _ => json!({}),
+        }
+    }
+
+    /// A `var` is described by the template of the variable it reads.
+    fn variable(&mut self, args: &'a Value) -> Value {
+        let name = match args {
+            Value::Array(args) => args.first().and_then(Value::as_str),
+            name => name.as_str(),
+        };
+        match name {
+            Some(INDEX_VARIABLE | RECORD_VARIABLE) => json!({"type": "integer", "minimum": 0}),
+            Some(name) if !self.resolving.contains(&name) => {
+                let Some((name, template)) = self.variables.get_key_value(name) else {
+                    return json!({});
+                };
+                self.resolving.push(name);
+                let schema = self.schema(template);
+                self.resolving.pop();
+                schema
+            }
+            _ => json!({}),
+        }
+    }
+}
+
+/// The template inside `{"optional": [probability, template]}`.
+fn optional_template(template: &Value) -> Option<&Value> {
+    match template {
+        Value::Object(obj) if obj.len() == 1 => obj
+            .get("optional")
+            .and_then(|args| PlanNode::probability_args("optional", args).ok())
+            .map(|(_, item)| item),
+        _ => None,
+    }
+}
+
+fn fake(args: &[Value]) -> Value {
+    let method = args.first().and_then(Value::as_str).unwrap_or_default();
+    if NUMERIC_METHODS.contains(&method) {
+        return numeric(method, args);
+    }
+    match method {
+        "bool" | "boolean" => json!({"type": "boolean"}),
+        "uuid" => json!({"type": "string", "format": "uuid"}),
+        "latitude" => json!({"type": "number", "minimum": -90, "maximum": 90}),
+        "longitude" => json!({"type": "number", "minimum": -180, "maximum": 180}),
+        "email" | "safe_email" | "free_email" => json!({"type": "string", "format": "email"}),
+        "domain_name" => json!({"type": "string", "format": "hostname"}),
+        "ipv4" => json!({"type": "string", "format": "ipv4"}),
+        "ipv6" => json!({"type": "string", "format": "ipv6"}),
+        "datetime" | "iso8601_datetime" | "date" => datetime::json_schema(args),
+        "time" => json!({"type": "string", "pattern": "^[0-9]{2}:[0-9]{2}:[0-9]{2}$"}),
+        "month_name" => json!({"enum": MONTH_NAMES}),
+        "lei" => json!({"type": "string", "pattern": "^[0-9A-Z]{18}[0-9]{2}$"}),
+        "iban" => json!({"type": "string", "pattern": "^[A-Z]{2}[0-9]{2}[0-9A-Za-z]+$"}),
+        "password" => {
+            // The upper bound is exclusive
+            let min = args.get(1).and_then(Value::as_u64).unwrap_or(8);
+            let max = args.get(2).and_then(Value::as_u64).unwrap_or(20);
+            json!({"type": "string", "minLength": min, "maxLength": max.saturating_sub(1).max(min)})
+        }
+        "alphanumeric" => {
+            let min = args.get(1).and_then(Value::as_u64).unwrap_or(10);
+            let max = args.get(2).and_then(Value::as_u64).unwrap_or(min);
+            json!({"type": "string", "pattern": "^[0-9A-Z]*$", "minLength": min, "maxLength": max})
+        }
+        "regex" => match args.get(1).and_then(Value::as_str) {
+            Some(pattern) if pattern.starts_with('^') && pattern.ends_with('$') => {
+                json!({"type": "string", "pattern": pattern})
+            }
+            Some(pattern) => json!({"type": "string", "pattern": format!("^(?:{pattern})$")}),
+            None => json!({"type": "string"}),
+        },
+        "enum" | "pick" | "choice" => enum_schema(args.iter().skip(1).cloned().collect()),
+        "weighted" | "weighted_choice" => match FakeOperator::weighted_options(args) {
+            Ok((values, weights)) => enum_schema(
+                values
+                    .into_iter()
+                    .zip(weights)
+                    .filter(|(_, weight)| *weight > 0.0)
+                    .map(|(value, _)| value.clone())
+                    .collect(),
+            ),
+            Err(_) => json!({}),
+        },
+        _ => json!({"type": "string"}),
+    }
+}
+
+/// Numeric methods are bounded by their `min`/`max` arguments, or by the range
+/// of their type. A trailing distribution object doesn't change the bounds.
+fn numeric(method: &str, args: &[Value]) -> Value {
+    let args = match args.last() {
+        Some(Value::Object(_)) => &args[..args.len() - 1],
+        _ => args,
+    };
+    let (kind, range) = match method {
+        "u8" => ("integer", Some((json!(u8::MIN), json!(u8::MAX)))),
+        "u16" => ("integer", Some((json!(u16::MIN), json!(u16::MAX)))),
+        "u32" => ("integer", Some((json!(u32::MIN), json!(u32::MAX)))),
+        "u64" => ("integer", Some((json!(u64::MIN), json!(u64::MAX)))),
+        "i8" => ("integer", Some((json!(i8::MIN), json!(i8::MAX)))),
+        "i16" => ("integer", Some((json!(i16::MIN), json!(i16::MAX)))),
+        "i32" => ("integer", Some((json!(i32::MIN), json!(i32::MAX)))),
+        "i64" => ("integer", Some((json!(i64::MIN), json!(i64::MAX)))),
+        _ => ("number", None),
+    };
+    let range = match args {
+        [_, min, max] if min.is_number() && max.is_number() => Some((min.clone(), max.clone())),
+        _ => range,
+    };
+    match range {
+        Some((min, max)) => json!({"type": kind, "minimum": min, "maximum": max}),
+        None => json!({"type": kind}),
+    }
+}
+
+fn sequence_schema(sequence: &Sequence) -> Value {
+    if sequence.is_numeric() {
+        return match sequence.step {
+            0 => json!({"const": sequence.start}),
+            step if step > 0 => json!({"type": "integer", "minimum": sequence.start}),
+            _ => json!({"type": "integer", "maximum": sequence.start}),
+        };
+    }
+    let sign = if sequence.start < 0 || sequence.step < 0 {
+        "-?"
+    } else {
+        ""
+    };
+    let digits = match sequence.pad {
+        0 => "+".to_string(),
+        pad => format!("{{{pad},}}"),
+    };
+    json!({
+        "type": "string",
+        "pattern": format!(
+            "^{}{sign}[0-9]{digits}{}$",
+            escape(&sequence.prefix),
+            escape(&sequence.suffix)
+        )
+    })
+}
+
+/// Escapes regex metacharacters in literal text.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn enum_schema(mut values: Vec<Value>) -> Value {
+    let mut seen = Vec::with_capacity(values.len());
+    values.retain(|value| {
+        let is_new = !seen.contains(value);
+        if is_new {
+            seen.push(value.clone());
+        }
+        is_new
+    });
+    json!({"enum": values})
+}
+
+/// A schema matching any of `schemas`, merging literals into one `enum`.
+fn any_of(schemas: Vec<Value>) -> Value {
+    let literals: Option<Vec<Vec<Value>>> = schemas
+        .iter()
+        .map(|schema| match schema.as_object() {
+            Some(obj) if obj.len() == 1 && obj.contains_key("const") => {
+                Some(vec![obj["const"].clone()])
+            }
+            Some(obj) if obj.len() == 1 => obj.get("enum").and_then(Value::as_array).cloned(),
+            _ => None,
+        })
+        .collect();
+    if let Some(literals) = literals {
+        return enum_schema(literals.into_iter().flatten().collect());
+    }
+
+    let mut distinct: Vec<Value> = Vec::with_capacity(schemas.len());
+    for schema in schemas {
+        if schema.as_object().is_some_and(Map::is_empty) {
+            return json!({});
+        }
+        if !distinct.contains(&schema) {
+            distinct.push(schema);
+        }
+    }
+    match distinct.len() {
+        1 => distinct.remove(0),
+        _ => json!({"anyOf": distinct}),
+    }
+}
+
+/// Allows `null` besides what `schema` matches.
+fn nullable(schema: Value) -> Value {
+    let Value::Object(mut obj) = schema else {
+        return schema;
+    };
+    if obj.is_empty() {
+        return Value::Object(obj);
+    }
+    if obj.len() == 1
+        && let Some(value) = obj.remove("const")
+    {
+        return json!({"enum": [value, null]});
+    }
+    if let Some(Value::Array(values)) = obj.get_mut("enum") {
+        values.push(Value::Null);
+        return Value::Object(obj);
+    }
+    if let Some(Value::String(kind)) = obj.get("type") {
+        let kinds = json!([kind, "null"]);
+        obj.insert("type".to_string(), kinds);
+        return Value::Object(obj);
+    }
+    json!({"anyOf": [obj, {"type": "null"}]})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConfigParser;
+
+    fn export(config: Value) -> Value {
+        export_json_schema(&ConfigParser::parse_value(config).unwrap())
+    }
+
+    #[test]
+    fn test_fields() {
+        let schema = export(json!({
+            "metadata": {"name": "Users"},
+            "variables": {"age": {"fake": ["u8", 18, 65]}},
+            "schema": {
+                "id": {"fake": ["uuid"]},
+                "email": {"fake": ["email"]},
+                "age": {"var": "age"},
+                "score": {"fake": ["f64", 0.0, 100.0, {"distribution": "normal", "mean": 50, "std_dev": 10}]},
+                "balance": {"fake": ["i32"]},
+                "plan": {"fake": ["choice", "free", "pro", "free"]},
+                "tier": {"one_of": ["gold", {"fake": ["weighted", ["silver", 3], ["bronze", 0]]}]},
+                "created": {"fake": ["datetime", "-30d", "now"]},
+                "epoch": {"fake": ["datetime", "-30d", "now", "unix"]},
+                "nickname": {"optional": [0.5, {"fake": ["username"]}]},
+                "manager": {"nullable": [0.2, {"fake": ["name"]}]},
+                "label": {"cat": ["user-", {"var": "age"}]},
+                "active": true
+            }
+        }));
+
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(schema["title"], "Users");
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["additionalProperties"], false);
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|key| key.as_str().unwrap())
+            .collect();
+        assert!(required.contains(&"id") && required.contains(&"manager"));
+        assert!(!required.contains(&"nickname"));
+
+        let properties = &schema["properties"];
+        assert_eq!(
+            properties["id"],
+            json!({"type": "string", "format": "uuid"})
+        );
+        assert_eq!(properties["email"]["format"], "email");
+        assert_eq!(
+            properties["age"],
+            json!({"type": "integer", "minimum": 18, "maximum": 65})
+        );
+        assert_eq!(
+            properties["score"],
+            json!({"type": "number", "minimum": 0.0, "maximum": 100.0})
+        );
+        assert_eq!(properties["balance"]["minimum"], i32::MIN);
+        assert_eq!(properties["plan"], json!({"enum": ["free", "pro"]}));
+        assert_eq!(properties["tier"], json!({"enum": ["gold", "silver"]}));
+        assert_eq!(properties["created"]["format"], "date-time");
+        assert_eq!(properties["epoch"], json!({"type": "integer"}));
+        assert_eq!(properties["nickname"], json!({"type": "string"}));
+        assert_eq!(properties["manager"], json!({"type": ["string", "null"]}));
+        assert_eq!(properties["label"], json!({"type": "string"}));
+        assert_eq!(properties["active"], json!({"const": true}));
+    }
+
+    #[test]
+    fn test_arrays_and_sequences() {
+        let schema = export(json!({
+            "schema": {
+                "id": {"sequence": {"start": 100}},
+                "code": {"sequence": {"prefix": "INV-", "pad": 6}},
+                "tags": {"repeat": [[1, 3], {"fake": ["word"]}]},
+                "pair": [{"fake": ["bool"]}, {"optional": [0.5, 1]}],
+                "item": {"repeat": [2, {"index": {"var": "$index"}}]}
+            }
+        }));
+        let properties = &schema["properties"];
+
+        assert_eq!(properties["id"], json!({"type": "integer", "minimum": 100}));
+        assert_eq!(properties["code"]["pattern"], "^INV-[0-9]{6,}$");
+        assert_eq!(
+            properties["tags"],
+            json!({"type": "array", "items": {"type": "string"}, "minItems": 1, "maxItems": 3})
+        );
+        assert_eq!(
+            properties["pair"],
+            json!({
+                "type": "array",
+                "prefixItems": [{"type": "boolean"}, {"const": 1}],
+                "items": false,
+                "minItems": 1,
+                "maxItems": 2
+            })
+        );
+        assert_eq!(
+            properties["item"]["items"]["properties"]["index"],
+            json!({"type": "integer", "minimum": 0})
+        );
+    }
+}
@@ -0,0 +1,5 @@
+//! Descriptions of generated data in other schema languages.
+
+pub mod json_schema;
+
+pub use json_schema::export_json_schema;
@@ -1,62 +1,292 @@
 use crate::config::ConfigParser;
-use crate::engine::Engine;
 use crate::error::Result;
-use crate::types::{DataFakeConfig, GenerationContext};
+use crate::export;
+use crate::import;
+use crate::output::{CsvOptions, CsvWriter, NdjsonWriter, RecordWriter, SqlOptions, SqlWriter};
+use crate::plan::ExecutionPlan;
+use crate::rng;
+use crate::types::DataFakeConfig;
 use serde_json::Value;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
 
 pub struct DataGenerator {
     config: DataFakeConfig,
+    plan: ExecutionPlan,
+    seed: Option<u64>,
+    threads: usize,
+    /// Index of the next record; each record's RNG is derived from it
+    next_index: AtomicU64,
 }
 
+/// Records generated per thread at a time by parallel streams.
+const PARALLEL_CHUNK: usize = 256;
+
 impl DataGenerator {
-    pub fn new(config: DataFakeConfig) -> Self {
-        Self { config }
+    /// Creates a generator, compiling the config into an execution plan that is
+    /// reused for every generated record. Fails if the config doesn't compile,
+    /// e.g. with an unknown locale or circular variables.
+    pub fn new(config: DataFakeConfig) -> Result<Self> {
+        let plan = ExecutionPlan::compile(&config)?;
+        let seed = config.metadata.as_ref().and_then(|metadata| metadata.seed);
+        Ok(Self {
+            config,
+            plan,
+            seed,
+            threads: 1,
+            next_index: AtomicU64::new(0),
+        })
+    }
+
+    /// Seeds the generator, overriding any `metadata.seed` from the config.
+    ///
+    /// Every random draw of subsequent generations comes from this seed, and
+    /// relative datetime bounds are resolved against
+    /// [`SEEDED_EPOCH`](crate::operators::datetime::SEEDED_EPOCH) unless
+    /// `metadata.now` sets the instant. Two generators built from the same config
+    /// and seed therefore produce identical output, on any day.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self {
+            plan: self.plan.seeded(),
+            seed: Some(seed),
+            ..self
+        }
+    }
+
+    /// Spreads batches and streams across `threads` threads (`0` uses all available
+    /// cores). Records keep their order, and seeded output is identical to a
+    /// single-threaded run.
+    pub fn with_threads(self, threads: usize) -> Self {
+        let threads = match threads {
+            0 => thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+        Self { threads, ..self }
+    }
+
+    /// Starts numbering records at `index`, so `generate`, `stream` and the writers
+    /// produce records `index..` of the dataset, e.g. for one shard of it.
+    pub fn starting_at(self, index: u64) -> Self {
+        Self {
+            next_index: AtomicU64::new(index),
+            ..self
+        }
     }
 
     pub fn from_json(json_str: &str) -> Result<Self> {
         let config = ConfigParser::parse(json_str)?;
-        Ok(Self::new(config))
+        Self::new(config)
     }
 
     pub fn from_value(json_value: Value) -> Result<Self> {
         let config = ConfigParser::parse_value(json_value)?;
-        Ok(Self::new(config))
+        Self::new(config)
+    }
+
+    /// Creates a generator producing values that match a JSON Schema, see
+    /// [`JsonSchemaImporter`](crate::import::JsonSchemaImporter).
+    pub fn from_json_schema(schema: &Value) -> Result<Self> {
+        let config = import::import_json_schema(schema)?;
+        Self::from_value(serde_json::to_value(config)?)
     }
 
     pub fn generate(&self) -> Result<Value> {
-        // First, convert HashMap to Map for engine
-        let variables_map: serde_json::Map<String, Value> = self
-            .config
-            .variables
-            .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect();
+        self.generate_indexed(self.reserve(1))
+    }
 
-        // Generate all variables
-        let generated_vars = Engine::generate_variables(&variables_map)?;
+    pub fn generate_batch(&self, count: usize) -> Result<Vec<Value>> {
+        self.generate_chunked(self.reserve(count), count)
+    }
 
-        // Create context with generated variables (convert back to HashMap)
-        let context = GenerationContext::with_variables(generated_vars.into_iter().collect());
+    /// Lazily generates `count` records. Single-threaded streams generate one
+    /// record per call to `next`; parallel streams generate a chunk at a time.
+    pub fn stream(&self, count: usize) -> RecordStream<'_> {
+        RecordStream {
+            generator: self,
+            remaining: count,
+            buffer: VecDeque::new(),
+        }
+    }
 
-        // Process the schema with the context
-        Engine::process_schema(&self.config.schema, &context)
+    /// Generates `count` records into `sink` without holding them in memory.
+    pub fn write_to<S: RecordWriter + ?Sized>(&self, sink: &mut S, count: usize) -> Result<()> {
+        for record in self.stream(count) {
+            sink.write_record(&record?)?;
+        }
+        sink.finish()
     }
 
-    pub fn generate_batch(&self, count: usize) -> Result<Vec<Value>> {
-        let mut results = Vec::with_capacity(count);
+    /// Writes `count` records to `writer` as newline-delimited JSON.
+    pub fn write_ndjson<W: Write>(&self, writer: W, count: usize) -> Result<()> {
+        self.write_to(&mut NdjsonWriter::new(writer), count)
+    }
 
-        for _ in 0..count {
-            results.push(self.generate()?);
-        }
+    /// Writes `count` records to `writer` as CSV, with columns flattened from the schema.
+    pub fn write_csv<W: Write>(&self, writer: W, count: usize, options: CsvOptions) -> Result<()> {
+        self.write_to(
+            &mut CsvWriter::new(writer, &self.config.schema, options),
+            count,
+        )
+    }
 
-        Ok(results)
+    /// Writes `count` records to `writer` as SQL `INSERT` statements or a `COPY` block.
+    pub fn write_sql<W: Write>(&self, writer: W, count: usize, options: SqlOptions) -> Result<()> {
+        self.write_to(
+            &mut SqlWriter::new(writer, &self.config.schema, options)?,
+            count,
+        )
+    }
+
+    /// Forgets the values emitted so far by `unique` fields, e.g. before
+    /// generating a new, independent dataset.
+    pub fn reset_unique(&self) {
+        self.plan.reset_unique();
+    }
+
+    /// Restarts named sequence counters from their first value. Per-record
+    /// sequences follow the record index instead, see [`DataGenerator::starting_at`].
+    pub fn reset_counters(&self) {
+        self.plan.reset_counters();
+    }
+
+    /// Lets `ref`s to `entity` in this generator's config pick from `keys`.
+    pub(crate) fn bind_references(&self, entity: &str, keys: &Arc<Vec<Value>>) {
+        self.plan.bind_references(entity, keys);
+    }
+
+    /// Infers a JSON Schema of the records this generator produces, see
+    /// [`export_json_schema`](crate::export::export_json_schema).
+    pub fn json_schema(&self) -> Value {
+        export::export_json_schema(&self.config)
     }
 
     pub fn config(&self) -> &DataFakeConfig {
         &self.config
     }
+
+    /// Claims the indices of the next `count` records.
+    fn reserve(&self, count: usize) -> u64 {
+        self.next_index.fetch_add(count as u64, Ordering::Relaxed)
+    }
+
+    /// Generates record `index` of the dataset without generating the records
+    /// before it.
+    ///
+    /// With a seed, each record's RNG is derived from the seed and its index, so
+    /// this returns exactly the record a sequential run would produce at that
+    /// position. Without a seed the record is random. Does not affect the
+    /// position of `generate` or `stream`.
+    ///
+    /// `unique` fields still avoid every value emitted so far, so records of such
+    /// configs depend on what was generated before.
+    pub fn generate_at(&self, index: u64) -> Result<Value> {
+        self.generate_indexed(index)
+    }
+
+    /// Generates records `start..start + count` of the dataset. Disjoint ranges can
+    /// be generated independently, e.g. by separate workers.
+    pub fn generate_range(&self, start: u64, count: usize) -> Result<Vec<Value>> {
+        self.generate_chunked(start, count)
+    }
+
+    fn generate_indexed(&self, index: u64) -> Result<Value> {
+        let mut rng = self.seed.map(|seed| rng::record_rng(seed, index));
+        rng::with_installed(&mut rng, || self.plan.execute_at(index))
+    }
+
+    /// Generates records `start..start + count`, split across the configured threads.
+    fn generate_chunked(&self, start: u64, count: usize) -> Result<Vec<Value>> {
+        // Which record claims a unique value or counter value first depends on
+        // generation order, so such configs are generated sequentially
+        let threads = if self.plan.has_unique() || self.plan.has_counters() {
+            1
+        } else {
+            self.threads.min(count)
+        };
+        if threads <= 1 {
+            return (start..start + count as u64)
+                .map(|index| self.generate_indexed(index))
+                .collect();
+        }
+
+        let chunk = count.div_ceil(threads);
+        let chunks: Vec<Result<Vec<Value>>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..count)
+                .step_by(chunk)
+                .map(|offset| {
+                    let first = start + offset as u64;
+                    let last = start + count.min(offset + chunk) as u64;
+                    scope.spawn(move || {
+                        (first..last)
+                            .map(|index| self.generate_indexed(index))
+                            .collect()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect()
+        });
+
+        let mut records = Vec::with_capacity(count);
+        for chunk in chunks {
+            records.extend(chunk?);
+        }
+        Ok(records)
+    }
+}
+
+/// Iterator over generated records, created by [`DataGenerator::stream`].
+pub struct RecordStream<'a> {
+    generator: &'a DataGenerator,
+    remaining: usize,
+    buffer: VecDeque<Value>,
+}
+
+impl Iterator for RecordStream<'_> {
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(record) = self.buffer.pop_front() {
+            return Some(Ok(record));
+        }
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let generator = self.generator;
+        if generator.threads <= 1 {
+            self.remaining -= 1;
+            return Some(generator.generate());
+        }
+
+        let count = self.remaining.min(generator.threads * PARALLEL_CHUNK);
+        self.remaining -= count;
+        match generator.generate_chunked(generator.reserve(count), count) {
+            Ok(records) => {
+                self.buffer.extend(records);
+                self.buffer.pop_front().map(Ok)
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.buffer.len() + self.remaining;
+        (len, Some(len))
+    }
 }
 
+impl ExactSizeIterator for RecordStream<'_> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,6 +333,29 @@ mod tests {
         assert!(result["location"]["city"].is_string());
     }
 
+    #[test]
+    fn test_variables_built_from_other_variables() {
+        let config = json!({
+            "variables": {
+                "firstName": {"fake": ["first_name"]},
+                "lastName": {"fake": ["last_name"]},
+                "email": {"cat": [{"var": "firstName"}, ".", {"var": "lastName"}, "@example.com"]}
+            },
+            "schema": {
+                "first": {"var": "firstName"},
+                "last": {"var": "lastName"},
+                "email": {"var": "email"}
+            }
+        });
+
+        let generator = DataGenerator::from_value(config).unwrap();
+        for result in generator.generate_batch(5).unwrap() {
+            let first = result["first"].as_str().unwrap();
+            let last = result["last"].as_str().unwrap();
+            assert_eq!(result["email"], format!("{first}.{last}@example.com"));
+        }
+    }
+
     #[test]
     fn test_generate_batch() {
         let config_json = r#"{
@@ -175,4 +428,366 @@ mod tests {
             result["metadata"]["createdAt"]
         );
     }
+
+    #[test]
+    fn test_seeded_generation_is_reproducible() {
+        let config = json!({
+            "variables": {
+                "userId": {"fake": ["uuid"]},
+                "score": {"fake": ["f64", 0, 1]}
+            },
+            "schema": {
+                "id": {"var": "userId"},
+                "name": {"fake": ["name"]},
+                "email": {"fake": ["email"]},
+                "age": {"fake": ["u8", 18, 65]},
+                "password": {"fake": ["password", 10, 15]},
+                "bio": {"fake": ["paragraph", 2, 3]},
+                "status": {"fake": ["enum", "active", "inactive", "pending"]},
+                "iban": {"fake": ["iban", "DE"]},
+                "tier": {"if": [{">": [{"var": "score"}, 0.5]}, {"fake": ["word"]}, "free"]},
+                "createdAt": {"fake": ["datetime"]},
+                "birthday": {"fake": ["date"]}
+            }
+        });
+
+        let first = DataGenerator::from_value(config.clone())
+            .unwrap()
+            .with_seed(42)
+            .generate_batch(10)
+            .unwrap();
+        let second = DataGenerator::from_value(config.clone())
+            .unwrap()
+            .with_seed(42)
+            .generate_batch(10)
+            .unwrap();
+        assert_eq!(
+            serde_json::to_string(&first).unwrap(),
+            serde_json::to_string(&second).unwrap()
+        );
+
+        // Records within a batch still differ from one another
+        assert_ne!(first[0], first[1]);
+
+        let other = DataGenerator::from_value(config)
+            .unwrap()
+            .with_seed(7)
+            .generate_batch(10)
+            .unwrap();
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_seeded_relative_datetimes() {
+        let config = json!({
+            "variables": {"joined": {"fake": ["datetime", "-30d", "now", "unix_ms"]}},
+            "schema": {
+                "joined": {"var": "joined"},
+                "nested": {"cat": ["on ", {"fake": ["date", "-1w", "today"]}]}
+            }
+        });
+        let generate = || {
+            DataGenerator::from_value(config.clone())
+                .unwrap()
+                .with_seed(21)
+                .generate_batch(5)
+                .unwrap()
+        };
+
+        // Resolved against SEEDED_EPOCH, so these hold on any day
+        let records = generate();
+        assert_eq!(
+            records[0],
+            json!({"joined": 1733559062365_u64, "nested": "on 2024-12-26"})
+        );
+        assert_eq!(
+            records[1],
+            json!({"joined": 1733212936527_u64, "nested": "on 2024-12-29"})
+        );
+
+        // `metadata.now` pins the anchor for good
+        let pinned = json!({
+            "metadata": {"now": "2024-03-10T12:00:00Z"},
+            "schema": {"day": {"fake": ["date", "-1d", "-1d"]}}
+        });
+        let record = DataGenerator::from_value(pinned)
+            .unwrap()
+            .generate()
+            .unwrap();
+        assert_eq!(record["day"], "2024-03-09");
+    }
+
+    #[test]
+    fn test_seed_from_metadata() {
+        let config = json!({
+            "metadata": {"seed": 1234},
+            "schema": {
+                "id": {"fake": ["uuid"]},
+                "city": {"fake": ["city_name"]},
+                "joined": {"fake": ["date"]}
+            }
+        });
+
+        let first = DataGenerator::from_value(config.clone()).unwrap();
+        let second = DataGenerator::from_value(config).unwrap();
+        let record = first.generate().unwrap();
+        // The default `-1y..now` range is anchored at SEEDED_EPOCH, not today
+        assert_eq!(record["joined"], "2024-06-10");
+        assert_eq!(record, second.generate().unwrap());
+        assert_eq!(first.config().metadata.as_ref().unwrap().seed, Some(1234));
+    }
+
+    #[test]
+    fn test_default_locale_from_metadata() {
+        let config = json!({
+            "metadata": {"locale": "ja_JP"},
+            "schema": {
+                "lastName": {"fake": ["last_name"]},
+                "english": {"fake": ["last_name", "en"]},
+                "greeting": {"cat": ["Hi ", {"fake": ["last_name"]}]}
+            }
+        });
+
+        let generator = DataGenerator::from_value(config).unwrap();
+        let result = generator.generate().unwrap();
+
+        assert!(!result["lastName"].as_str().unwrap().is_ascii());
+        assert!(result["english"].as_str().unwrap().is_ascii());
+        assert!(!result["greeting"].as_str().unwrap().is_ascii());
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<DataGenerator>();
+
+        let config = json!({
+            "variables": {"userId": {"fake": ["uuid"]}},
+            "schema": {
+                "id": {"var": "userId"},
+                "name": {"fake": ["name"]},
+                "tags": {"repeat": [[0, 5], {"fake": ["word"]}]}
+            }
+        });
+        let sequential = DataGenerator::from_value(config.clone())
+            .unwrap()
+            .with_seed(9)
+            .generate_batch(1000)
+            .unwrap();
+
+        let parallel = DataGenerator::from_value(config.clone())
+            .unwrap()
+            .with_seed(9)
+            .with_threads(4)
+            .generate_batch(1000)
+            .unwrap();
+        assert_eq!(parallel, sequential);
+
+        let generator = DataGenerator::from_value(config)
+            .unwrap()
+            .with_seed(9)
+            .with_threads(3);
+        let stream = generator.stream(1000);
+        assert_eq!(stream.len(), 1000);
+        let streamed: Vec<Value> = stream.collect::<Result<_>>().unwrap();
+        assert_eq!(streamed, sequential);
+
+        let unseeded = DataGenerator::from_json(r#"{"schema": {"id": {"fake": ["uuid"]}}}"#)
+            .unwrap()
+            .with_threads(0);
+        let ids: std::collections::HashSet<String> = unseeded
+            .generate_batch(500)
+            .unwrap()
+            .into_iter()
+            .map(|record| record["id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(ids.len(), 500);
+    }
+
+    #[test]
+    fn test_unique_fields() {
+        let config = json!({
+            "schema": {
+                "id": {"unique": {"fake": ["u16", 1, 2000]}},
+                "email": {"unique": {"fake": ["email"]}},
+                "tags": {"repeat": [3, {"unique": [{"fake": ["u32", 1, 10000]}, 500]}]}
+            }
+        });
+        let generator = DataGenerator::from_value(config.clone())
+            .unwrap()
+            .with_seed(11)
+            .with_threads(4);
+        let records = generator.generate_batch(500).unwrap();
+
+        let ids: std::collections::HashSet<u64> =
+            records.iter().map(|r| r["id"].as_u64().unwrap()).collect();
+        assert_eq!(ids.len(), 500);
+        let tags: std::collections::HashSet<u64> = records
+            .iter()
+            .flat_map(|r| r["tags"].as_array().unwrap().clone())
+            .map(|tag| tag.as_u64().unwrap())
+            .collect();
+        assert_eq!(tags.len(), 1500);
+
+        // Seeded output is still reproducible
+        let again = DataGenerator::from_value(config)
+            .unwrap()
+            .with_seed(11)
+            .generate_batch(500)
+            .unwrap();
+        assert_eq!(records, again);
+
+        let generator = DataGenerator::from_json(
+            r#"{"schema": {"id": {"unique": [{"fake": ["u8", 1, 3]}, 1000]}}}"#,
+        )
+        .unwrap();
+        generator.generate_batch(3).unwrap();
+        assert!(matches!(
+            generator.generate(),
+            Err(crate::DataFakeError::UniqueValuesExhausted { .. })
+        ));
+        generator.reset_unique();
+        assert!(generator.generate().is_ok());
+    }
+
+    #[test]
+    fn test_generate_at() {
+        let config = json!({
+            "variables": {"userId": {"fake": ["uuid"]}},
+            "schema": {"id": {"var": "userId"}, "age": {"fake": ["u8", 18, 65]}}
+        });
+        let generator = DataGenerator::from_value(config).unwrap().with_seed(3);
+        let batch = generator.generate_batch(50).unwrap();
+
+        assert_eq!(generator.generate_at(37).unwrap(), batch[37]);
+        assert_eq!(generator.generate_at(0).unwrap(), batch[0]);
+        assert_eq!(generator.generate_range(10, 5).unwrap(), batch[10..15]);
+
+        // Shards cover the dataset without overlap
+        let shard = DataGenerator::new(generator.config().clone())
+            .unwrap()
+            .with_seed(3)
+            .starting_at(25);
+        let tail: Vec<Value> = shard.stream(25).collect::<Result<_>>().unwrap();
+        assert_eq!(tail, batch[25..]);
+    }
+
+    #[test]
+    fn test_sequences() {
+        let config = json!({
+            "schema": {
+                "id": {"sequence": {"start": 1}},
+                "invoice": {"sequence": {"prefix": "INV-", "pad": 6}}
+            }
+        });
+        let generator = DataGenerator::from_value(config.clone())
+            .unwrap()
+            .with_threads(4);
+        let records = generator.generate_batch(300).unwrap();
+        for (i, record) in records.iter().enumerate() {
+            assert_eq!(record["id"], i as u64 + 1);
+        }
+        assert_eq!(records[41]["invoice"], "INV-000042");
+        assert_eq!(generator.generate().unwrap()["id"], 301);
+        assert_eq!(generator.generate_at(9).unwrap()["id"], 10);
+
+        let shard = DataGenerator::from_value(config).unwrap().starting_at(1000);
+        assert_eq!(shard.generate().unwrap()["invoice"], "INV-001001");
+
+        // Named counters keep counting across calls until reset
+        let generator = DataGenerator::from_json(
+            r#"{"schema": {"items": {"repeat": [3, {"sequence": {"counter": "item"}}]}}}"#,
+        )
+        .unwrap()
+        .with_threads(4);
+        let records = generator.generate_batch(100).unwrap();
+        assert_eq!(records[0]["items"], json!([1, 2, 3]));
+        assert_eq!(records[99]["items"], json!([298, 299, 300]));
+        assert_eq!(generator.generate().unwrap()["items"][0], 301);
+        generator.reset_counters();
+        assert_eq!(generator.generate().unwrap()["items"][0], 1);
+    }
+
+    #[test]
+    fn test_stream() {
+        let generator = DataGenerator::from_json(r#"{"schema": {"id": {"fake": ["uuid"]}}}"#)
+            .unwrap()
+            .with_seed(5);
+        let stream = generator.stream(3);
+        assert_eq!(stream.len(), 3);
+
+        let streamed: Vec<Value> = stream.collect::<Result<_>>().unwrap();
+        let expected = DataGenerator::from_json(r#"{"schema": {"id": {"fake": ["uuid"]}}}"#)
+            .unwrap()
+            .with_seed(5)
+            .generate_batch(3)
+            .unwrap();
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_write_ndjson() {
+        let generator = DataGenerator::from_json(
+            r#"{"schema": {"id": {"fake": ["uuid"]}, "age": {"fake": ["u8", 18, 65]}}}"#,
+        )
+        .unwrap();
+
+        let mut output = Vec::new();
+        generator.write_ndjson(&mut output, 100).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 100);
+        for line in lines {
+            let record: Value = serde_json::from_str(line).unwrap();
+            assert_eq!(record["id"].as_str().unwrap().len(), 36);
+        }
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let generator = DataGenerator::from_json(
+            r#"{"schema": {"id": {"fake": ["uuid"]}, "profile": {"age": {"fake": ["u8", 18, 65]}}}}"#,
+        )
+        .unwrap();
+
+        let mut output = Vec::new();
+        generator
+            .write_csv(&mut output, 10, CsvOptions::default())
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[0], "id,profile.age");
+        for line in &lines[1..] {
+            let (id, age) = line.split_once(',').unwrap();
+            assert_eq!(id.len(), 36);
+            assert!((18..=65).contains(&age.parse::<u8>().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_write_sql() {
+        let generator = DataGenerator::from_json(
+            r#"{"schema": {"id": {"fake": ["uuid"]}, "age": {"fake": ["u8", 18, 65]}}}"#,
+        )
+        .unwrap();
+
+        let options = SqlOptions {
+            batch_size: 4,
+            create_table: true,
+            ..SqlOptions::new("users")
+        };
+        let mut output = Vec::new();
+        generator.write_sql(&mut output, 10, options).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(
+            output.starts_with(
+                "CREATE TABLE \"users\" (\n    \"age\" SMALLINT,\n    \"id\" UUID\n);\n"
+            )
+        );
+        assert_eq!(output.matches("INSERT INTO").count(), 3);
+    }
 }
@@ -0,0 +1,595 @@
+use crate::error::{DataFakeError, Result};
+use crate::types::{DataFakeConfig, Metadata};
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeSet, HashMap};
+
+/// Probability that a property missing from `required` is left out of a record.
+pub const DEFAULT_OPTIONAL_PROBABILITY: f64 = 0.3;
+
+/// Probability that a nullable value (`"type": [..., "null"]` or
+/// `"nullable": true`) is `null`.
+pub const DEFAULT_NULL_PROBABILITY: f64 = 0.1;
+
+/// Converts a JSON Schema document into a config producing matching values.
+pub fn import_json_schema(schema: &Value) -> Result<DataFakeConfig> {
+    JsonSchemaImporter::new(schema).import()
+}
+
+/// Converts JSON Schemas into datafake templates.
+///
+/// Supports `type` (including type arrays with `null`), `format`, `enum`,
+/// `const`, `minimum`/`maximum` (and their exclusive forms), `multipleOf` for
+/// integers, `minLength`/`maxLength`, `pattern`, `properties` with `required`,
+/// `items`/`prefixItems` with `minItems`/`maxItems`, `allOf`, `oneOf`/`anyOf`,
+/// and local `$ref`s resolved against the root document.
+///
+/// Recursive `$ref`s are cut off: a recursive property that isn't required is
+/// left out and a recursive array is empty. A required recursive property is an
+/// error, as no finite value satisfies it.
+pub struct JsonSchemaImporter<'a> {
+    root: &'a Value,
+    optional_probability: f64,
+    null_probability: f64,
+    /// Properties whose schema sets this flag (e.g. `readOnly`) are left out
+    excluded_flag: Option<&'static str>,
+    /// `$ref`s being expanded
+    expanding: Vec<String>,
+}
+
+impl<'a> JsonSchemaImporter<'a> {
+    /// Creates an importer whose `$ref`s resolve against `root`.
+    pub fn new(root: &'a Value) -> Self {
+        Self {
+            root,
+            optional_probability: DEFAULT_OPTIONAL_PROBABILITY,
+            null_probability: DEFAULT_NULL_PROBABILITY,
+            excluded_flag: None,
+            expanding: Vec::new(),
+        }
+    }
+
+    /// Sets how often properties that aren't `required` are left out (`0` always
+    /// includes them).
+    pub fn with_optional_probability(self, probability: f64) -> Self {
+        Self {
+            optional_probability: probability.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Sets how often nullable values are `null`.
+    pub fn with_null_probability(self, probability: f64) -> Self {
+        Self {
+            null_probability: probability.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Leaves out properties whose schema has `flag` set to `true`, such as
+    /// `readOnly` properties in request payloads.
+    pub(crate) fn excluding(self, flag: &'static str) -> Self {
+        Self {
+            excluded_flag: Some(flag),
+            ..self
+        }
+    }
+
+    /// Converts the root schema into a config, taking its name and description
+    /// from `title` and `description`.
+    pub fn import(mut self) -> Result<DataFakeConfig> {
+        let root = self.root;
+        let text = |key: &str| root.get(key).and_then(Value::as_str).map(str::to_string);
+        let (name, description) = (text("title"), text("description"));
+        let metadata = (name.is_some() || description.is_some()).then(|| Metadata {
+            name,
+            version: None,
+            description,
+            seed: None,
+            locale: None,
+            now: None,
+            extra: HashMap::new(),
+        });
+
+        Ok(DataFakeConfig {
+            metadata,
+            variables: HashMap::new(),
+            schema: self.import_schema(root)?,
+        })
+    }
+
+    /// Converts `schema`, a schema within the root document, into a datafake
+    /// template.
+    pub fn import_schema(&mut self, schema: &Value) -> Result<Value> {
+        let Value::Object(keywords) = schema else {
+            return match schema {
+                Value::Bool(true) => Ok(json!({"fake": ["word"]})),
+                _ => Err(DataFakeError::InvalidConfig(format!(
+                    "Cannot generate values for the JSON Schema {schema}"
+                ))),
+            };
+        };
+
+        if let Some(Value::String(reference)) = keywords.get("$ref") {
+            let target = self.resolve(reference)?;
+            self.expanding.push(reference.clone());
+            let template = self.import_schema(target);
+            self.expanding.pop();
+            return template;
+        }
+        if let Some(value) = keywords.get("const") {
+            return Ok(literal(value));
+        }
+        if let Some(Value::Array(values)) = keywords.get("enum") {
+            let mut args = vec![Value::from("enum")];
+            args.extend(values.iter().cloned());
+            return Ok(json!({ "fake": args }));
+        }
+        if let Some(Value::Array(schemas)) = keywords.get("allOf") {
+            let merged = self.merge_all_of(keywords, schemas)?;
+            return self.import_schema(&merged);
+        }
+        if let Some(Value::Array(schemas)) = keywords.get("oneOf").or(keywords.get("anyOf")) {
+            return self.import_alternatives(schemas);
+        }
+
+        let (kind, nullable) = schema_type(keywords);
+        let template = match kind {
+            "object" => self.import_object(keywords)?,
+            "array" => self.import_array(keywords)?,
+            "integer" => import_integer(keywords)?,
+            "number" => import_number(keywords)?,
+            "boolean" => json!({"fake": ["bool"]}),
+            "null" => null_template(),
+            _ => import_string(keywords),
+        };
+        Ok(if nullable && kind != "null" {
+            json!({"nullable": [self.null_probability, template]})
+        } else {
+            template
+        })
+    }
+
+    /// Looks up a local `$ref` such as `#/$defs/Address` in the root document.
+    fn resolve(&self, reference: &str) -> Result<&'a Value> {
+        let pointer = reference.strip_prefix('#').ok_or_else(|| {
+            DataFakeError::InvalidConfig(format!(
+                "Only local $refs (starting with '#') are supported, got '{reference}'"
+            ))
+        })?;
+        self.root.pointer(pointer).ok_or_else(|| {
+            DataFakeError::InvalidConfig(format!("$ref '{reference}' does not resolve"))
+        })
+    }
+
+    /// Whether a property schema sets the excluded flag, directly or through its
+    /// `$ref`.
+    fn is_excluded(&self, schema: &Value) -> Result<bool> {
+        let Some(flag) = self.excluded_flag else {
+            return Ok(false);
+        };
+        let schema = match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => self.resolve(reference)?,
+            None => schema,
+        };
+        Ok(schema.get(flag) == Some(&Value::Bool(true)))
+    }
+
+    /// Whether `schema` is a `$ref` already being expanded, i.e. recursion.
+    fn is_recursive(&self, schema: &Value) -> bool {
+        schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .is_some_and(|reference| self.expanding.iter().any(|r| r == reference))
+    }
+
+    /// Combines `allOf` subschemas into one: properties and `required` are
+    /// unioned, other keywords are taken from the last subschema defining them.
+    fn merge_all_of(&self, keywords: &Map<String, Value>, schemas: &[Value]) -> Result<Value> {
+        let mut merged = keywords.clone();
+        merged.remove("allOf");
+        let mut properties = match merged.remove("properties") {
+            Some(Value::Object(properties)) => properties,
+            _ => Map::new(),
+        };
+        let mut required: BTreeSet<String> = string_set(merged.get("required"));
+
+        for schema in schemas {
+            let schema = match schema.get("$ref").and_then(Value::as_str) {
+                Some(reference) => self.resolve(reference)?,
+                None => schema,
+            };
+            let Value::Object(subschema) = schema else {
+                continue;
+            };
+            for (key, value) in subschema {
+                match key.as_str() {
+                    "properties" => {
+                        if let Value::Object(more) = value {
+                            properties.extend(more.clone());
+                        }
+                    }
+                    "required" => required.extend(string_set(Some(value))),
+                    _ => {
+                        merged.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+
+        if !properties.is_empty() {
+            merged.insert("properties".to_string(), Value::Object(properties));
+        }
+        if !required.is_empty() {
+            merged.insert("required".to_string(), json!(required));
+        }
+        Ok(Value::Object(merged))
+    }
+
+    fn import_alternatives(&mut self, schemas: &[Value]) -> Result<Value> {
+        let mut templates = Vec::with_capacity(schemas.len());
+        for schema in schemas {
+            if !self.is_recursive(schema) {
+                templates.push(self.import_schema(schema)?);
+            }
+        }
+        match templates.len() {
+            0 => Err(DataFakeError::InvalidConfig(
+                "Every oneOf/anyOf alternative is recursive".to_string(),
+            )),
+            1 => Ok(templates.remove(0)),
+            _ => Ok(json!({ "one_of": templates })),
+        }
+    }
+
+    fn import_object(&mut self, keywords: &Map<String, Value>) -> Result<Value> {
+        let required = string_set(keywords.get("required"));
+        let mut template = Map::new();
+        if let Some(Value::Object(properties)) = keywords.get("properties") {
+            for (name, schema) in properties {
+                if self.is_excluded(schema)? {
+                    continue;
+                }
+                let is_required = required.contains(name);
+                if self.is_recursive(schema) {
+                    if is_required {
+                        return Err(DataFakeError::InvalidConfig(format!(
+                            "Required property '{name}' refers back to its own schema, so no \
+                             finite value exists"
+                        )));
+                    }
+                    continue;
+                }
+                let value = self.import_schema(schema)?;
+                let value = if is_required || self.optional_probability == 0.0 {
+                    value
+                } else {
+                    json!({"optional": [self.optional_probability, value]})
+                };
+                template.insert(name.clone(), value);
+            }
+        }
+        Ok(Value::Object(template))
+    }
+
+    fn import_array(&mut self, keywords: &Map<String, Value>) -> Result<Value> {
+        // Tuples: `prefixItems` (2020-12) or an array of `items` (earlier drafts)
+        if let Some(Value::Array(schemas)) = keywords.get("prefixItems").or(keywords.get("items")) {
+            return schemas
+                .iter()
+                .map(|schema| self.import_schema(schema))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array);
+        }
+
+        let min = integer(keywords, "minItems").unwrap_or(1).max(0) as u64;
+        let max = integer(keywords, "maxItems").map_or(min + 2, |max| max.max(0) as u64);
+        let min = min.min(max);
+        let item = match keywords.get("items") {
+            Some(schema) if self.is_recursive(schema) => return Ok(json!([])),
+            Some(schema) => self.import_schema(schema)?,
+            None => json!({"fake": ["word"]}),
+        };
+        let count = if min == max {
+            json!(min)
+        } else {
+            json!([min, max])
+        };
+        Ok(json!({"repeat": [count, item]}))
+    }
+}
+
+/// The schema's type, and whether `null` is also allowed. Without `type`, the
+/// type is guessed from the keywords present.
+fn schema_type(keywords: &Map<String, Value>) -> (&str, bool) {
+    let nullable = keywords.get("nullable") == Some(&Value::Bool(true));
+    match keywords.get("type") {
+        Some(Value::String(kind)) => (kind, nullable),
+        Some(Value::Array(kinds)) => {
+            let allows_null = kinds.iter().any(|kind| kind == "null");
+            let kind = kinds
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|kind| *kind != "null")
+                .unwrap_or("null");
+            (kind, nullable || allows_null)
+        }
+        _ => {
+            let has = |key: &str| keywords.contains_key(key);
+            let kind = if has("properties") || has("required") {
+                "object"
+            } else if has("items") || has("prefixItems") || has("minItems") {
+                "array"
+            } else if has("minimum") || has("maximum") || has("multipleOf") {
+                "number"
+            } else {
+                "string"
+            };
+            (kind, nullable)
+        }
+    }
+}
+
+fn import_string(keywords: &Map<String, Value>) -> Value {
+    let format = keywords.get("format").and_then(Value::as_str);
+    let method = match format {
+        Some("email" | "idn-email") => Some("email"),
+        Some("uuid") => Some("uuid"),
+        Some("date-time") => Some("datetime"),
+        Some("date") => Some("date"),
+        Some("time") => Some("time"),
+        Some("ipv4") => Some("ipv4"),
+        Some("ipv6") => Some("ipv6"),
+        Some("hostname" | "idn-hostname") => Some("domain_name"),
+        _ => None,
+    };
+    if let Some(method) = method {
+        return json!({"fake": [method]});
+    }
+    if matches!(format, Some("uri" | "url" | "iri")) {
+        return json!({"fake": ["regex", r"https://[a-z]{3,10}\.(com|net|org)/[a-z]{3,8}"]});
+    }
+    if let Some(Value::String(pattern)) = keywords.get("pattern") {
+        return json!({"fake": ["regex", pattern]});
+    }
+
+    let min = integer(keywords, "minLength").map(|n| n.max(0));
+    let max = integer(keywords, "maxLength").map(|n| n.max(0));
+    match (min, max) {
+        (None, None) => json!({"fake": ["word"]}),
+        (min, max) => {
+            let min = min.unwrap_or(1).min(max.unwrap_or(i64::MAX));
+            let max = max.unwrap_or(min.max(1) + 10);
+            json!({"fake": ["alphanumeric", min, max]})
+        }
+    }
+}
+
+/// Range used when a numeric schema is unbounded on one or both sides.
+const DEFAULT_SPAN: i64 = 1000;
+
+fn import_integer(keywords: &Map<String, Value>) -> Result<Value> {
+    let (min, max) = bounds(keywords, 1.0);
+    let (min, max) = match (min.map(|n| n.ceil() as i64), max.map(|n| n.floor() as i64)) {
+        (Some(min), Some(max)) => (min, max),
+        (Some(min), None) => (min, min.saturating_add(DEFAULT_SPAN)),
+        (None, Some(max)) => (max.saturating_sub(DEFAULT_SPAN).min(0), max),
+        (None, None) => (0, DEFAULT_SPAN),
+    };
+
+    match keywords.get("multipleOf").and_then(Value::as_i64) {
+        Some(step) if step > 1 => {
+            let (low, high) = (
+                min.div_euclid(step) + (min.rem_euclid(step) != 0) as i64,
+                max.div_euclid(step),
+            );
+            check_range(low as f64, high as f64)?;
+            Ok(json!({"*": [{"fake": ["i64", low, high]}, step]}))
+        }
+        _ => {
+            check_range(min as f64, max as f64)?;
+            Ok(json!({"fake": ["i64", min, max]}))
+        }
+    }
+}
+
+fn import_number(keywords: &Map<String, Value>) -> Result<Value> {
+    let (min, max) = bounds(keywords, 0.01);
+    let span = DEFAULT_SPAN as f64;
+    let (min, max) = match (min, max) {
+        (Some(min), Some(max)) => (min, max),
+        (Some(min), None) => (min, min + span),
+        (None, Some(max)) => (max.min(0.0) - span, max),
+        (None, None) => (0.0, span),
+    };
+    check_range(min, max)?;
+    Ok(json!({"fake": ["f64", min, max, {"decimals": 2}]}))
+}
+
+/// Inclusive numeric bounds, moving exclusive bounds inwards by `step`. Both the
+/// draft 4 boolean form and the later numeric form of `exclusiveMinimum` are read.
+fn bounds(keywords: &Map<String, Value>, step: f64) -> (Option<f64>, Option<f64>) {
+    let number = |key: &str| keywords.get(key).and_then(Value::as_f64);
+    let flag = |key: &str| keywords.get(key) == Some(&Value::Bool(true));
+
+    let min = match (number("minimum"), number("exclusiveMinimum")) {
+        (_, Some(exclusive)) => Some(exclusive + step),
+        (Some(min), None) if flag("exclusiveMinimum") => Some(min + step),
+        (min, None) => min,
+    };
+    let max = match (number("maximum"), number("exclusiveMaximum")) {
+        (_, Some(exclusive)) => Some(exclusive - step),
+        (Some(max), None) if flag("exclusiveMaximum") => Some(max - step),
+        (max, None) => max,
+    };
+    (min, max)
+}
+
+fn check_range(min: f64, max: f64) -> Result<()> {
+    if min > max {
+        return Err(DataFakeError::InvalidRange { min, max });
+    }
+    Ok(())
+}
+
+fn integer(keywords: &Map<String, Value>, key: &str) -> Option<i64> {
+    keywords.get(key).and_then(Value::as_i64)
+}
+
+fn string_set(value: Option<&Value>) -> BTreeSet<String> {
+    match value {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => BTreeSet::new(),
+    }
+}
+
+/// A template producing exactly `value`.
+pub(crate) fn literal(value: &Value) -> Value {
+    match value {
+        Value::Null => null_template(),
+        // Objects could be read as expressions, so constants go through `enum`
+        Value::Object(_) | Value::Array(_) => json!({"fake": ["enum", value]}),
+        other => other.clone(),
+    }
+}
+
+/// Schema values can't be `null` themselves, so `null` is a value that is always
+/// nulled.
+pub(crate) fn null_template() -> Value {
+    json!({"nullable": [1, ""]})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataGenerator;
+
+    fn generate(schema: Value, count: usize) -> Vec<Value> {
+        let config = import_json_schema(&schema).unwrap();
+        DataGenerator::from_value(serde_json::to_value(config).unwrap())
+            .unwrap()
+            .with_seed(1)
+            .generate_batch(count)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_import_object() {
+        let records = generate(
+            json!({
+                "title": "User",
+                "type": "object",
+                "required": ["id", "email", "age", "role", "tags"],
+                "properties": {
+                    "id": {"type": "string", "format": "uuid"},
+                    "email": {"type": "string", "format": "email"},
+                    "age": {"type": "integer", "minimum": 18, "exclusiveMaximum": 66},
+                    "score": {"type": "number", "minimum": 0, "maximum": 1},
+                    "role": {"enum": ["admin", "member"]},
+                    "code": {"type": "string", "pattern": "^[A-Z]{3}-\\d{4}$"},
+                    "nickname": {"type": "string", "minLength": 3, "maxLength": 8},
+                    "tags": {"type": "array", "items": {"type": "string"}, "minItems": 2, "maxItems": 4},
+                    "deleted_at": {"type": ["string", "null"], "format": "date-time"}
+                }
+            }),
+            100,
+        );
+
+        for record in &records {
+            assert_eq!(record["id"].as_str().unwrap().len(), 36);
+            assert!(record["email"].as_str().unwrap().contains('@'));
+            assert!((18..=65).contains(&record["age"].as_i64().unwrap()));
+            assert!(["admin", "member"].contains(&record["role"].as_str().unwrap()));
+            assert!((2..=4).contains(&record["tags"].as_array().unwrap().len()));
+            if let Some(code) = record.get("code") {
+                assert_eq!(code.as_str().unwrap().len(), 8);
+            }
+            if let Some(nickname) = record.get("nickname") {
+                assert!((3..=8).contains(&nickname.as_str().unwrap().len()));
+            }
+            if let Some(score) = record.get("score") {
+                assert!((0.0..=1.0).contains(&score.as_f64().unwrap()));
+            }
+        }
+        // Properties that aren't required are sometimes left out
+        assert!(records.iter().any(|r| r.get("score").is_none()));
+        assert!(records.iter().any(|r| r.get("score").is_some()));
+        assert!(records.iter().any(|r| r["deleted_at"].is_null()));
+
+        let config = import_json_schema(&json!({"title": "User", "type": "object"})).unwrap();
+        assert_eq!(config.metadata.unwrap().name.as_deref(), Some("User"));
+    }
+
+    #[test]
+    fn test_refs_and_combinators() {
+        let records = generate(
+            json!({
+                "$defs": {
+                    "Address": {
+                        "type": "object",
+                        "required": ["city"],
+                        "properties": {"city": {"type": "string"}}
+                    },
+                    "Node": {
+                        "type": "object",
+                        "required": ["value"],
+                        "properties": {
+                            "value": {"type": "integer", "multipleOf": 5, "minimum": 1, "maximum": 50},
+                            "children": {"type": "array", "items": {"$ref": "#/$defs/Node"}},
+                            "parent": {"$ref": "#/$defs/Node"}
+                        }
+                    }
+                },
+                "type": "object",
+                "required": ["home", "tree", "contact", "billing"],
+                "properties": {
+                    "home": {"$ref": "#/$defs/Address"},
+                    "tree": {"$ref": "#/$defs/Node"},
+                    "contact": {"oneOf": [
+                        {"type": "string", "format": "email"},
+                        {"type": "integer", "const": 0}
+                    ]},
+                    "billing": {"allOf": [
+                        {"$ref": "#/$defs/Address"},
+                        {"required": ["zip"], "properties": {"zip": {"pattern": "^\\d{5}$"}}}
+                    ]}
+                }
+            }),
+            50,
+        );
+
+        for record in &records {
+            assert!(record["home"]["city"].is_string());
+            assert!(record["billing"]["city"].is_string());
+            assert_eq!(record["billing"]["zip"].as_str().unwrap().len(), 5);
+            let value = record["tree"]["value"].as_i64().unwrap();
+            assert!(value % 5 == 0 && (5..=50).contains(&value));
+            // Recursion is cut off
+            if let Some(children) = record["tree"].get("children") {
+                assert_eq!(children, &json!([]));
+            }
+            assert!(record["tree"].get("parent").is_none());
+        }
+        assert!(records.iter().any(|r| r["contact"] == 0));
+        assert!(records.iter().any(|r| r["contact"].is_string()));
+    }
+
+    #[test]
+    fn test_invalid_schemas() {
+        let recursive = json!({
+            "$defs": {"Loop": {"type": "object", "required": ["next"], "properties": {"next": {"$ref": "#/$defs/Loop"}}}},
+            "$ref": "#/$defs/Loop"
+        });
+        assert!(import_json_schema(&recursive).is_err());
+        assert!(import_json_schema(&json!({"$ref": "other.json#/Pet"})).is_err());
+        assert!(import_json_schema(&json!({"$ref": "#/missing"})).is_err());
+        assert!(matches!(
+            import_json_schema(&json!({"type": "integer", "minimum": 10, "maximum": 5})),
+            Err(DataFakeError::InvalidRange { .. })
+        ));
+        assert!(import_json_schema(&json!(false)).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! Converters from other schema languages, and from sample data, into
+//! [`DataFakeConfig`](crate::DataFakeConfig)s.
+
+pub mod json_schema;
+pub mod openapi;
+pub mod sample;
+
+pub use json_schema::{JsonSchemaImporter, import_json_schema};
+pub use openapi::{OpenApiSpec, Payload, PayloadKind};
+pub use sample::{SampleProfiler, infer_config};
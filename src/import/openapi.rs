@@ -0,0 +1,328 @@
+use crate::error::{DataFakeError, Result};
+use crate::generator::DataGenerator;
+use crate::import::JsonSchemaImporter;
+use crate::types::{DataFakeConfig, Metadata};
+use serde_json::Value;
+use std::collections::HashMap;
+
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Which payload of an operation a [`Payload`] describes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayloadKind {
+    RequestBody,
+    /// A response, by status code as written in the spec (`200`, `4XX`, `default`)
+    Response(String),
+}
+
+/// A request body or response schema of an OpenAPI operation, converted into a
+/// config.
+#[derive(Debug, Clone)]
+pub struct Payload {
+    /// `operationId`, or `METHOD /path` when the operation has none
+    pub operation: String,
+    /// Uppercase HTTP method
+    pub method: String,
+    pub path: String,
+    pub kind: PayloadKind,
+    pub media_type: String,
+    pub config: DataFakeConfig,
+}
+
+impl Payload {
+    /// Creates a generator producing this payload.
+    pub fn generator(&self) -> Result<DataGenerator> {
+        DataGenerator::from_value(serde_json::to_value(&self.config)?)
+    }
+}
+
+/// The payload schemas of an OpenAPI 3 document.
+///
+/// Every operation's JSON request body and JSON responses are converted with
+/// [`JsonSchemaImporter`], with `$ref`s resolved against the whole document.
+/// `readOnly` properties are left out of request bodies and `writeOnly`
+/// properties out of responses. Operations are named by their `operationId`, or
+/// `METHOD /path` without one.
+#[derive(Debug, Clone)]
+pub struct OpenApiSpec {
+    payloads: Vec<Payload>,
+}
+
+impl OpenApiSpec {
+    pub fn from_json(json_str: &str) -> Result<Self> {
+        let document: Value = serde_json::from_str(json_str)
+            .map_err(|e| DataFakeError::ConfigParse(format!("Failed to parse JSON: {e}")))?;
+        Self::parse(&document)
+    }
+
+    pub fn parse(document: &Value) -> Result<Self> {
+        let version = document.get("openapi").and_then(Value::as_str);
+        if !version.is_some_and(|version| version.starts_with("3.")) {
+            return Err(DataFakeError::ConfigParse(format!(
+                "Expected an OpenAPI 3 document, got version {}",
+                version.unwrap_or("(none)")
+            )));
+        }
+
+        let mut payloads = Vec::new();
+        let Some(Value::Object(paths)) = document.get("paths") else {
+            return Ok(Self { payloads });
+        };
+        for (path, item) in paths {
+            let item = resolve(document, item)?;
+            for method in HTTP_METHODS {
+                let Some(operation) = item.get(*method) else {
+                    continue;
+                };
+                let method = method.to_ascii_uppercase();
+                let name = operation
+                    .get("operationId")
+                    .and_then(Value::as_str)
+                    .map_or_else(|| format!("{method} {path}"), str::to_string);
+                let mut add = |kind: PayloadKind, body: &Value, excluded| -> Result<()> {
+                    let Some((media_type, schema)) = json_content(document, body)? else {
+                        return Ok(());
+                    };
+                    let template = JsonSchemaImporter::new(document)
+                        .excluding(excluded)
+                        .import_schema(schema)?;
+                    payloads.push(Payload {
+                        operation: name.clone(),
+                        method: method.clone(),
+                        path: path.clone(),
+                        kind,
+                        media_type,
+                        config: config(document, &name, template),
+                    });
+                    Ok(())
+                };
+
+                if let Some(body) = operation.get("requestBody") {
+                    add(PayloadKind::RequestBody, body, "readOnly")?;
+                }
+                if let Some(Value::Object(responses)) = operation.get("responses") {
+                    for (status, response) in responses {
+                        add(PayloadKind::Response(status.clone()), response, "writeOnly")?;
+                    }
+                }
+            }
+        }
+        Ok(Self { payloads })
+    }
+
+    /// Every payload, in path and then method order.
+    pub fn payloads(&self) -> &[Payload] {
+        &self.payloads
+    }
+
+    /// The request body of `operation` (an `operationId` or `METHOD /path`).
+    pub fn request_body(&self, operation: &str) -> Option<&Payload> {
+        self.find(operation, &PayloadKind::RequestBody)
+    }
+
+    /// The response of `operation` for `status` (e.g. `"200"` or `"default"`).
+    pub fn response(&self, operation: &str, status: &str) -> Option<&Payload> {
+        self.find(operation, &PayloadKind::Response(status.to_string()))
+    }
+
+    fn find(&self, operation: &str, kind: &PayloadKind) -> Option<&Payload> {
+        self.payloads
+            .iter()
+            .find(|payload| payload.operation == operation && payload.kind == *kind)
+    }
+}
+
+/// Follows a `$ref` to a request body, response or path item.
+fn resolve<'a>(document: &'a Value, value: &'a Value) -> Result<&'a Value> {
+    match value.get("$ref").and_then(Value::as_str) {
+        Some(reference) => reference
+            .strip_prefix('#')
+            .and_then(|pointer| document.pointer(pointer))
+            .ok_or_else(|| {
+                DataFakeError::InvalidConfig(format!("$ref '{reference}' does not resolve"))
+            }),
+        None => Ok(value),
+    }
+}
+
+/// The first JSON media type of a request body or response that has a schema.
+fn json_content<'a>(document: &'a Value, body: &'a Value) -> Result<Option<(String, &'a Value)>> {
+    let body = resolve(document, body)?;
+    let Some(Value::Object(content)) = body.get("content") else {
+        return Ok(None);
+    };
+    Ok(content.iter().find_map(|(media_type, media)| {
+        let essence = media_type.split(';').next().unwrap_or_default().trim();
+        let is_json = essence == "application/json" || essence.ends_with("+json");
+        match media.get("schema") {
+            Some(schema) if is_json => Some((media_type.clone(), schema)),
+            _ => None,
+        }
+    }))
+}
+
+fn config(document: &Value, operation: &str, schema: Value) -> DataFakeConfig {
+    let version = document
+        .pointer("/info/version")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    DataFakeConfig {
+        metadata: Some(Metadata {
+            name: Some(operation.to_string()),
+            version,
+            description: None,
+            seed: None,
+            locale: None,
+            now: None,
+            extra: HashMap::new(),
+        }),
+        variables: HashMap::new(),
+        schema,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn petstore() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Petstore", "version": "1.2.0"},
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "responses": {
+                            "200": {
+                                "description": "A list of pets",
+                                "content": {"application/json": {"schema": {
+                                    "type": "array",
+                                    "items": {"$ref": "#/components/schemas/Pet"},
+                                    "maxItems": 5
+                                }}}
+                            },
+                            "default": {"$ref": "#/components/responses/Error"}
+                        }
+                    },
+                    "post": {
+                        "requestBody": {
+                            "content": {"application/json; charset=utf-8": {
+                                "schema": {"$ref": "#/components/schemas/Pet"}
+                            }}
+                        },
+                        "responses": {
+                            "201": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}},
+                            "204": {"description": "No content"}
+                        }
+                    }
+                },
+                "/pets/{id}/photo": {
+                    "put": {
+                        "operationId": "uploadPhoto",
+                        "requestBody": {"content": {"image/png": {"schema": {"type": "string", "format": "binary"}}}},
+                        "responses": {"200": {"description": "ok"}}
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["id", "name", "status"],
+                        "properties": {
+                            "id": {"type": "integer", "format": "int64", "readOnly": true, "minimum": 1},
+                            "name": {"type": "string", "minLength": 1, "maxLength": 20},
+                            "status": {"type": "string", "enum": ["available", "sold"]},
+                            "tag": {"type": "string", "nullable": true},
+                            "secret": {"type": "string", "writeOnly": true}
+                        }
+                    }
+                },
+                "responses": {
+                    "Error": {"content": {"application/problem+json": {"schema": {
+                        "type": "object",
+                        "required": ["code", "message"],
+                        "properties": {"code": {"type": "integer"}, "message": {"type": "string"}}
+                    }}}}
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_payloads() {
+        let spec = OpenApiSpec::parse(&petstore()).unwrap();
+        let names: Vec<(&str, &PayloadKind)> = spec
+            .payloads()
+            .iter()
+            .map(|payload| (payload.operation.as_str(), &payload.kind))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("listPets", &PayloadKind::Response("200".to_string())),
+                ("listPets", &PayloadKind::Response("default".to_string())),
+                ("POST /pets", &PayloadKind::RequestBody),
+                ("POST /pets", &PayloadKind::Response("201".to_string())),
+            ]
+        );
+
+        let list = spec.response("listPets", "200").unwrap();
+        assert_eq!(list.method, "GET");
+        assert_eq!(list.path, "/pets");
+        assert_eq!(
+            list.config.metadata.as_ref().unwrap().version.as_deref(),
+            Some("1.2.0")
+        );
+        let pets = list.generator().unwrap().generate().unwrap();
+        for pet in pets.as_array().unwrap() {
+            assert!(pet["id"].as_i64().unwrap() >= 1);
+            assert!(["available", "sold"].contains(&pet["status"].as_str().unwrap()));
+            assert!(pet.get("secret").is_none());
+        }
+
+        let error = spec.response("listPets", "default").unwrap();
+        assert_eq!(error.media_type, "application/problem+json");
+        let error = error.generator().unwrap().generate().unwrap();
+        assert!(error["code"].is_i64() && error["message"].is_string());
+    }
+
+    #[test]
+    fn test_read_only_and_write_only() {
+        let spec = OpenApiSpec::parse(&petstore()).unwrap();
+        let request = spec
+            .request_body("POST /pets")
+            .unwrap()
+            .generator()
+            .unwrap();
+        let response = spec
+            .response("POST /pets", "201")
+            .unwrap()
+            .generator()
+            .unwrap();
+        for _ in 0..20 {
+            let body = request.generate().unwrap();
+            assert!(body.get("id").is_none());
+            assert!(body["name"].is_string());
+
+            let created = response.generate().unwrap();
+            assert!(created["id"].is_i64());
+            assert!(created.get("secret").is_none());
+        }
+
+        assert!(spec.request_body("uploadPhoto").is_none());
+        assert!(spec.response("POST /pets", "204").is_none());
+    }
+
+    #[test]
+    fn test_rejects_other_documents() {
+        assert!(OpenApiSpec::parse(&json!({"swagger": "2.0", "paths": {}})).is_err());
+        assert!(OpenApiSpec::from_json("{").is_err());
+        let empty = OpenApiSpec::parse(&json!({"openapi": "3.1.0"})).unwrap();
+        assert!(empty.payloads().is_empty());
+    }
+}
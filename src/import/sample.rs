@@ -0,0 +1,626 @@
+use crate::error::{DataFakeError, Result};
+use crate::import::json_schema::{literal, null_template};
+use crate::types::DataFakeConfig;
+use chrono::{DateTime, NaiveDate, NaiveTime, SecondsFormat, Utc};
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, HashMap};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Most distinct values a field may have to be generated as an enum.
+pub const DEFAULT_MAX_ENUM_VALUES: usize = 10;
+
+/// Field names that suggest a fake method for free-form strings, compared
+/// lowercase without separators.
+const FIELD_HINTS: &[(&[&str], &str)] = &[
+    (&["firstname", "givenname", "forename"], "first_name"),
+    (&["lastname", "surname", "familyname"], "last_name"),
+    (&["name", "fullname", "displayname"], "name"),
+    (&["username", "login", "handle"], "username"),
+    (
+        &["phone", "phonenumber", "telephone", "mobile"],
+        "phone_number",
+    ),
+    (&["street", "streetaddress", "address"], "street_address"),
+    (&["city", "town"], "city"),
+    (&["state", "province"], "state_name"),
+    (&["country", "countryname"], "country_name"),
+    (&["countrycode"], "country_code"),
+    (&["zip", "zipcode"], "zip_code"),
+    (&["postcode", "postalcode"], "post_code"),
+    (&["company", "companyname", "employer"], "company_name"),
+    (&["jobtitle", "profession", "occupation"], "profession"),
+    (&["industry"], "industry"),
+    (&["domain", "domainname", "hostname"], "domain_name"),
+    (&["useragent"], "user_agent"),
+    (&["mac", "macaddress"], "mac_address"),
+    (&["iban"], "iban"),
+    (&["bic", "swift"], "bic"),
+    (&["lei"], "lei"),
+    (&["currency", "currencycode"], "currency_code"),
+    (
+        &["cardnumber", "creditcard", "creditcardnumber"],
+        "credit_card_number",
+    ),
+    (&["password"], "password"),
+    (&["filename"], "file_name"),
+    (&["filepath"], "file_path"),
+];
+
+/// Proposes a config producing records like `samples`.
+pub fn infer_config(samples: &[Value]) -> Result<DataFakeConfig> {
+    let mut profiler = SampleProfiler::new();
+    for sample in samples {
+        profiler.add(sample);
+    }
+    profiler.infer()
+}
+
+/// Profiles sample records and proposes a config producing similar ones.
+///
+/// Strings that are all UUIDs, emails, IP addresses, RFC 3339 datetimes, dates
+/// or times use the matching fake method, with dates bounded by the observed
+/// range. Strings and integers with few distinct, repeated values become a
+/// `weighted` choice using the observed frequencies. Other strings are mapped
+/// by field name (`first_name`, `city`, `phone`, ...) or by shape (codes, words,
+/// sentences). Numbers keep their observed range, arrays their length range, and
+/// object keys missing from some samples and `null`s keep their observed rates.
+#[derive(Debug, Clone)]
+pub struct SampleProfiler {
+    max_enum_values: usize,
+    root: Profile,
+}
+
+impl Default for SampleProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SampleProfiler {
+    pub fn new() -> Self {
+        Self {
+            max_enum_values: DEFAULT_MAX_ENUM_VALUES,
+            root: Profile::default(),
+        }
+    }
+
+    /// Sets the most distinct values a field may have to be generated as an
+    /// enum (`0` never infers enums).
+    pub fn with_max_enum_values(self, max_enum_values: usize) -> Self {
+        Self {
+            max_enum_values,
+            ..self
+        }
+    }
+
+    /// Adds a sample record to the profile.
+    pub fn add(&mut self, record: &Value) {
+        self.root.add(record, self.max_enum_values);
+    }
+
+    /// Proposes a config from the records added so far.
+    pub fn infer(&self) -> Result<DataFakeConfig> {
+        if self.root.seen == 0 {
+            return Err(DataFakeError::InvalidConfig(
+                "no sample records to infer a config from".to_string(),
+            ));
+        }
+        Ok(DataFakeConfig {
+            metadata: None,
+            variables: HashMap::new(),
+            schema: self.root.template(None),
+        })
+    }
+}
+
+/// What was seen at one position of the samples (the record, an object key or
+/// the elements of an array).
+#[derive(Debug, Clone, Default)]
+struct Profile {
+    seen: usize,
+    nulls: usize,
+    booleans: usize,
+    trues: usize,
+    numbers: Option<NumberProfile>,
+    strings: Option<StringProfile>,
+    arrays: Option<ArrayProfile>,
+    objects: Option<ObjectProfile>,
+}
+
+#[derive(Debug, Clone)]
+struct NumberProfile {
+    count: usize,
+    values: ValueCounts,
+    /// Range of integer values; `None` once a fraction is seen
+    integers: Option<(i128, i128)>,
+    min: f64,
+    max: f64,
+    decimals: usize,
+}
+
+#[derive(Debug, Clone)]
+struct StringProfile {
+    count: usize,
+    values: ValueCounts,
+    kinds: BTreeMap<StringKind, usize>,
+    min_length: usize,
+    max_length: usize,
+    min_words: usize,
+    max_words: usize,
+    total_words: usize,
+    datetimes: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    dates: Option<(NaiveDate, NaiveDate)>,
+}
+
+#[derive(Debug, Clone)]
+struct ArrayProfile {
+    min_length: usize,
+    max_length: usize,
+    items: Box<Profile>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ObjectProfile {
+    count: usize,
+    fields: BTreeMap<String, Profile>,
+}
+
+/// Distinct values with how often each was seen, until there are too many to
+/// be an enum.
+#[derive(Debug, Clone, Default)]
+struct ValueCounts {
+    counts: Vec<(Value, usize)>,
+    overflowed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum StringKind {
+    Uuid,
+    Email,
+    Ipv4,
+    Ipv6,
+    DateTime,
+    Date,
+    Time,
+    /// Uppercase letters and digits, e.g. `AB12CD`
+    Code,
+    /// Text without whitespace
+    Word,
+    /// Several words ending in a period
+    Sentence,
+    Text,
+}
+
+impl Profile {
+    fn add(&mut self, value: &Value, max_enum_values: usize) {
+        self.seen += 1;
+        match value {
+            Value::Null => self.nulls += 1,
+            Value::Bool(b) => {
+                self.booleans += 1;
+                self.trues += usize::from(*b);
+            }
+            Value::Number(n) => {
+                let number = n.as_f64().unwrap_or_default();
+                let integer = n
+                    .as_i64()
+                    .map(i128::from)
+                    .or_else(|| n.as_u64().map(i128::from));
+                let decimals = n
+                    .to_string()
+                    .split_once('.')
+                    .filter(|(_, fraction)| !fraction.contains(['e', 'E']))
+                    .map_or(0, |(_, fraction)| fraction.len());
+                let profile = self.numbers.get_or_insert_with(|| NumberProfile {
+                    count: 0,
+                    values: ValueCounts::default(),
+                    integers: integer.map(|i| (i, i)),
+                    min: number,
+                    max: number,
+                    decimals: 0,
+                });
+                profile.count += 1;
+                profile.min = profile.min.min(number);
+                profile.max = profile.max.max(number);
+                profile.decimals = profile.decimals.max(decimals);
+                profile.integers = match (profile.integers, integer) {
+                    (Some((min, max)), Some(i)) => Some((min.min(i), max.max(i))),
+                    _ => None,
+                };
+                if integer.is_some() {
+                    profile.values.add(value, max_enum_values);
+                } else {
+                    profile.values.overflowed = true;
+                }
+            }
+            Value::String(s) => {
+                let kind = StringKind::classify(s);
+                let length = s.chars().count();
+                let words = s.split_whitespace().count();
+                let profile = self.strings.get_or_insert_with(|| StringProfile {
+                    count: 0,
+                    values: ValueCounts::default(),
+                    kinds: BTreeMap::new(),
+                    min_length: length,
+                    max_length: length,
+                    min_words: words,
+                    max_words: words,
+                    total_words: 0,
+                    datetimes: None,
+                    dates: None,
+                });
+                profile.count += 1;
+                profile.values.add(value, max_enum_values);
+                *profile.kinds.entry(kind).or_default() += 1;
+                profile.min_length = profile.min_length.min(length);
+                profile.max_length = profile.max_length.max(length);
+                profile.min_words = profile.min_words.min(words);
+                profile.max_words = profile.max_words.max(words);
+                profile.total_words += words;
+                if let Ok(datetime) = DateTime::parse_from_rfc3339(s) {
+                    let datetime = datetime.with_timezone(&Utc);
+                    profile.datetimes = Some(extend(profile.datetimes, datetime));
+                }
+                if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+                    profile.dates = Some(extend(profile.dates, date));
+                }
+            }
+            Value::Array(items) => {
+                let profile = self.arrays.get_or_insert_with(|| ArrayProfile {
+                    min_length: items.len(),
+                    max_length: items.len(),
+                    items: Box::default(),
+                });
+                profile.min_length = profile.min_length.min(items.len());
+                profile.max_length = profile.max_length.max(items.len());
+                for item in items {
+                    profile.items.add(item, max_enum_values);
+                }
+            }
+            Value::Object(fields) => {
+                let profile = self.objects.get_or_insert_with(ObjectProfile::default);
+                profile.count += 1;
+                for (key, value) in fields {
+                    profile
+                        .fields
+                        .entry(key.clone())
+                        .or_default()
+                        .add(value, max_enum_values);
+                }
+            }
+        }
+    }
+
+    /// A template for the values seen here; `name` is the enclosing object key.
+    fn template(&self, name: Option<&str>) -> Value {
+        let mut alternatives = Vec::new();
+        if self.booleans > 0 {
+            alternatives.push(match self.trues {
+                0 if self.booleans > 1 => json!(false),
+                trues if trues == self.booleans && trues > 1 => json!(true),
+                _ => json!({"fake": ["bool"]}),
+            });
+        }
+        if let Some(numbers) = &self.numbers {
+            alternatives.push(numbers.template());
+        }
+        if let Some(strings) = &self.strings {
+            alternatives.push(strings.template(name));
+        }
+        if let Some(arrays) = &self.arrays {
+            alternatives.push(arrays.template(name));
+        }
+        if let Some(objects) = &self.objects {
+            alternatives.push(objects.template());
+        }
+
+        let template = match alternatives.len() {
+            0 => return null_template(),
+            1 => alternatives.remove(0),
+            _ => json!({"one_of": alternatives}),
+        };
+        if self.nulls == 0 {
+            return template;
+        }
+        json!({"nullable": [rate(self.nulls, self.seen), template]})
+    }
+}
+
+impl NumberProfile {
+    fn template(&self) -> Value {
+        if let Some(choice) = self.values.choice(self.count) {
+            return choice;
+        }
+        match self.integers {
+            Some((min, max)) if min == max => json!(min as i64),
+            Some((min, max)) if max > i64::MAX as i128 => {
+                json!({"fake": ["u64", min as u64, max as u64]})
+            }
+            Some((min, max)) => json!({"fake": ["i64", min as i64, max as i64]}),
+            None if self.decimals <= 6 => {
+                json!({"fake": ["f64", self.min, self.max, {"decimals": self.decimals}]})
+            }
+            None => json!({"fake": ["f64", self.min, self.max]}),
+        }
+    }
+}
+
+impl StringProfile {
+    fn template(&self, name: Option<&str>) -> Value {
+        let kind = match self.kinds.keys().collect::<Vec<_>>()[..] {
+            [kind] => *kind,
+            _ if self.max_words <= 1 => StringKind::Word,
+            _ => StringKind::Text,
+        };
+
+        let method = match kind {
+            StringKind::Uuid => return json!({"fake": ["uuid"]}),
+            StringKind::Email => return json!({"fake": ["email"]}),
+            StringKind::Ipv4 => return json!({"fake": ["ipv4"]}),
+            StringKind::Ipv6 => return json!({"fake": ["ipv6"]}),
+            StringKind::Time => return json!({"fake": ["time"]}),
+            StringKind::DateTime => {
+                let (start, end) = self.datetimes.unwrap_or_default();
+                let format = |dt: DateTime<Utc>| dt.to_rfc3339_opts(SecondsFormat::Secs, true);
+                return json!({"fake": ["datetime", format(start), format(end)]});
+            }
+            StringKind::Date => {
+                let (start, end) = self.dates.unwrap_or_default();
+                let format = |date: NaiveDate| date.format("%Y-%m-%d").to_string();
+                return json!({"fake": ["date", format(start), format(end)]});
+            }
+            _ => self.values.choice(self.count),
+        };
+        if let Some(choice) = method {
+            return choice;
+        }
+        if let Some(method) = name.and_then(hinted_method) {
+            return json!({"fake": [method]});
+        }
+
+        match kind {
+            StringKind::Code => {
+                json!({"fake": ["alphanumeric", self.min_length, self.max_length]})
+            }
+            StringKind::Sentence => {
+                json!({"fake": ["sentence", self.min_words, self.max_words + 1]})
+            }
+            StringKind::Text => {
+                let words = (self.total_words as f64 / self.count as f64).round() as usize;
+                json!({"fake": ["words", words.max(1)]})
+            }
+            _ => json!({"fake": ["word"]}),
+        }
+    }
+}
+
+impl ArrayProfile {
+    fn template(&self, name: Option<&str>) -> Value {
+        if self.items.seen == 0 {
+            return json!([]);
+        }
+        let count = if self.min_length == self.max_length {
+            json!(self.min_length)
+        } else {
+            json!([self.min_length, self.max_length])
+        };
+        json!({"repeat": [count, self.items.template(name)]})
+    }
+}
+
+impl ObjectProfile {
+    fn template(&self) -> Value {
+        let mut template = Map::new();
+        for (key, profile) in &self.fields {
+            let mut field = profile.template(Some(key));
+            if profile.seen < self.count {
+                field = json!({"optional": [rate(self.count - profile.seen, self.count), field]});
+            }
+            template.insert(key.clone(), field);
+        }
+        Value::Object(template)
+    }
+}
+
+impl ValueCounts {
+    fn add(&mut self, value: &Value, max_values: usize) {
+        if self.overflowed {
+            return;
+        }
+        match self.counts.iter().position(|(seen, _)| seen == value) {
+            Some(i) => self.counts[i].1 += 1,
+            None if self.counts.len() < max_values => self.counts.push((value.clone(), 1)),
+            None => {
+                self.overflowed = true;
+                self.counts = Vec::new();
+            }
+        }
+    }
+
+    /// A weighted choice of the observed values, if they repeat enough to look
+    /// like an enum rather than a sample of free-form values.
+    fn choice(&self, total: usize) -> Option<Value> {
+        if self.overflowed || self.counts.is_empty() || self.counts.len() * 2 > total {
+            return None;
+        }
+        if let [(value, _)] = &self.counts[..] {
+            return Some(literal(value));
+        }
+        let mut counts = self.counts.clone();
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        let mut args = vec![json!("weighted")];
+        args.extend(
+            counts
+                .into_iter()
+                .map(|(value, count)| json!([value, count])),
+        );
+        Some(json!({"fake": args}))
+    }
+}
+
+impl StringKind {
+    fn classify(s: &str) -> Self {
+        if is_uuid(s) {
+            StringKind::Uuid
+        } else if is_email(s) {
+            StringKind::Email
+        } else if s.parse::<Ipv4Addr>().is_ok() {
+            StringKind::Ipv4
+        } else if s.contains(':') && s.parse::<Ipv6Addr>().is_ok() {
+            StringKind::Ipv6
+        } else if DateTime::parse_from_rfc3339(s).is_ok() {
+            StringKind::DateTime
+        } else if NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
+            StringKind::Date
+        } else if NaiveTime::parse_from_str(s, "%H:%M:%S").is_ok() {
+            StringKind::Time
+        } else if !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        {
+            StringKind::Code
+        } else if !s.contains(char::is_whitespace) {
+            StringKind::Word
+        } else if s.ends_with('.') {
+            StringKind::Sentence
+        } else {
+            StringKind::Text
+        }
+    }
+}
+
+fn is_uuid(s: &str) -> bool {
+    s.len() == 36
+        && s.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+fn is_email(s: &str) -> bool {
+    match s.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !s.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+fn hinted_method(name: &str) -> Option<&'static str> {
+    let name: String = name
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    FIELD_HINTS
+        .iter()
+        .find(|(names, _)| names.contains(&name.as_str()))
+        .map(|(_, method)| *method)
+}
+
+fn extend<T: PartialOrd + Copy>(range: Option<(T, T)>, value: T) -> (T, T) {
+    match range {
+        Some((min, max)) => (
+            if value < min { value } else { min },
+            if value > max { value } else { max },
+        ),
+        None => (value, value),
+    }
+}
+
+/// `part / total`, rounded to two decimals.
+fn rate(part: usize, total: usize) -> f64 {
+    (part as f64 / total as f64 * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataGenerator;
+
+    #[test]
+    fn test_detects_formats_and_ranges() {
+        let samples: Vec<Value> = (0..20)
+            .map(|i| {
+                json!({
+                    "id": format!("8c5f0a4e-2b1d-4c3a-9e7f-{:012x}", i),
+                    "email": format!("user{i}@example.com"),
+                    "ip": format!("10.0.0.{i}"),
+                    "created_at": format!("2024-03-{:02}T12:00:00Z", i + 1),
+                    "birthday": format!("1990-01-{:02}", i + 1),
+                    "status": if i % 4 == 0 { "inactive" } else { "active" },
+                    "age": 20 + i,
+                    "score": 1.5 + i as f64 / 4.0,
+                    "tags": vec!["x"; i % 3 + 1],
+                    "first_name": format!("Name{i}"),
+                    "nickname": if i % 2 == 0 { json!(format!("nick{i}")) } else { Value::Null },
+                })
+            })
+            .collect();
+        let mut samples = samples;
+        samples[0].as_object_mut().unwrap().remove("age");
+
+        let schema = infer_config(&samples).unwrap().schema;
+        assert_eq!(schema["id"], json!({"fake": ["uuid"]}));
+        assert_eq!(schema["email"], json!({"fake": ["email"]}));
+        assert_eq!(schema["ip"], json!({"fake": ["ipv4"]}));
+        assert_eq!(
+            schema["created_at"],
+            json!({"fake": ["datetime", "2024-03-01T12:00:00Z", "2024-03-20T12:00:00Z"]})
+        );
+        assert_eq!(
+            schema["birthday"],
+            json!({"fake": ["date", "1990-01-01", "1990-01-20"]})
+        );
+        assert_eq!(
+            schema["status"],
+            json!({"fake": ["weighted", ["active", 15], ["inactive", 5]]})
+        );
+        assert_eq!(
+            schema["age"],
+            json!({"optional": [0.05, {"fake": ["i64", 21, 39]}]})
+        );
+        assert_eq!(
+            schema["score"],
+            json!({"fake": ["f64", 1.5, 6.25, {"decimals": 2}]})
+        );
+        assert_eq!(schema["tags"], json!({"repeat": [[1, 3], "x"]}));
+        assert_eq!(schema["first_name"], json!({"fake": ["first_name"]}));
+        assert_eq!(
+            schema["nickname"],
+            json!({"nullable": [0.5, {"fake": ["word"]}]})
+        );
+    }
+
+    #[test]
+    fn test_inferred_config_generates() {
+        let samples = [
+            json!({"name": "Ada Lovelace", "code": "AB12", "items": [{"sku": 1, "qty": 2}], "note": "Ships soon."}),
+            json!({"name": "Alan Turing", "code": "CD345", "items": [], "note": "Left at the door.", "gift": true}),
+            json!({"name": "Grace Hopper", "code": "EF6", "items": [{"sku": 2, "qty": 1.5}], "note": null}),
+        ];
+        let config = infer_config(&samples).unwrap();
+        assert_eq!(config.schema["name"], json!({"fake": ["name"]}));
+        assert_eq!(
+            config.schema["code"],
+            json!({"fake": ["alphanumeric", 3, 5]})
+        );
+        assert_eq!(config.schema["items"]["repeat"][0], json!([0, 1]));
+
+        let generator = DataGenerator::from_value(serde_json::to_value(&config).unwrap()).unwrap();
+        for record in generator.generate_batch(20).unwrap() {
+            assert!(record["name"].is_string());
+            let code = record["code"].as_str().unwrap();
+            assert!((3..=5).contains(&code.len()));
+            assert!(record["items"].as_array().unwrap().len() <= 1);
+            assert!(record["note"].is_null() || record["note"].is_string());
+        }
+
+        assert!(infer_config(&[]).is_err());
+    }
+}
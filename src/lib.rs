@@ -1,11 +1,22 @@
 pub mod config;
+pub mod dataset;
 pub mod engine;
 pub mod error;
+pub mod export;
 pub mod generator;
+pub mod import;
+pub mod locale;
 pub mod operators;
+pub mod output;
+pub mod plan;
+mod rng;
 pub mod types;
 
 pub use config::ConfigParser;
-pub use error::{DataFakeError, Result};
-pub use generator::DataGenerator;
-pub use types::{DataFakeConfig, GenerationContext, Metadata};
+pub use dataset::DatasetGenerator;
+pub use error::{ConfigIssue, DataFakeError, Result};
+pub use generator::{DataGenerator, RecordStream};
+pub use locale::Locale;
+pub use output::RecordWriter;
+pub use plan::ExecutionPlan;
+pub use types::{DataFakeConfig, DatasetConfig, EntityConfig, GenerationContext, Metadata};
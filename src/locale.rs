@@ -0,0 +1,119 @@
+use crate::error::{DataFakeError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Locales supported by the underlying `fake` crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    En,
+    FrFr,
+    DeDe,
+    ItIt,
+    PtBr,
+    PtPt,
+    JaJp,
+    ZhCn,
+    ZhTw,
+    ArSa,
+    CyGb,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 11] = [
+        Locale::En,
+        Locale::FrFr,
+        Locale::DeDe,
+        Locale::ItIt,
+        Locale::PtBr,
+        Locale::PtPt,
+        Locale::JaJp,
+        Locale::ZhCn,
+        Locale::ZhTw,
+        Locale::ArSa,
+        Locale::CyGb,
+    ];
+
+    /// Canonical code, e.g. `fr_FR`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::FrFr => "fr_FR",
+            Locale::DeDe => "de_DE",
+            Locale::ItIt => "it_IT",
+            Locale::PtBr => "pt_BR",
+            Locale::PtPt => "pt_PT",
+            Locale::JaJp => "ja_JP",
+            Locale::ZhCn => "zh_CN",
+            Locale::ZhTw => "zh_TW",
+            Locale::ArSa => "ar_SA",
+            Locale::CyGb => "cy_GB",
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = DataFakeError;
+
+    /// Parses codes case-insensitively with `_` or `-` as separator. A bare language
+    /// is accepted where it is unambiguous, and any English region maps to `en`.
+    fn from_str(code: &str) -> Result<Self> {
+        let normalized = code.replace('-', "_").to_ascii_lowercase();
+        let locale = match normalized.as_str() {
+            "en" => Locale::En,
+            other if other.starts_with("en_") => Locale::En,
+            "fr" | "fr_fr" => Locale::FrFr,
+            "de" | "de_de" => Locale::DeDe,
+            "it" | "it_it" => Locale::ItIt,
+            "pt_br" => Locale::PtBr,
+            "pt_pt" => Locale::PtPt,
+            "ja" | "ja_jp" => Locale::JaJp,
+            "zh_cn" => Locale::ZhCn,
+            "zh_tw" => Locale::ZhTw,
+            "ar" | "ar_sa" => Locale::ArSa,
+            "cy" | "cy_gb" => Locale::CyGb,
+            _ => {
+                let supported: Vec<&str> = Locale::ALL.iter().map(Locale::code).collect();
+                return Err(DataFakeError::InvalidLocale(format!(
+                    "{code} (supported: {})",
+                    supported.join(", ")
+                )));
+            }
+        };
+        Ok(locale)
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_locale_codes() {
+        assert_eq!("en_US".parse::<Locale>().unwrap(), Locale::En);
+        assert_eq!("fr_FR".parse::<Locale>().unwrap(), Locale::FrFr);
+        assert_eq!("de-de".parse::<Locale>().unwrap(), Locale::DeDe);
+        assert_eq!("ja".parse::<Locale>().unwrap(), Locale::JaJp);
+        assert_eq!("pt_BR".parse::<Locale>().unwrap(), Locale::PtBr);
+
+        for locale in Locale::ALL {
+            assert_eq!(locale.code().parse::<Locale>().unwrap(), locale);
+        }
+    }
+
+    #[test]
+    fn test_invalid_locale() {
+        assert!(matches!(
+            "xx_YY".parse::<Locale>(),
+            Err(DataFakeError::InvalidLocale(_))
+        ));
+        // Ambiguous without a region
+        assert!("pt".parse::<Locale>().is_err());
+        assert!("zh".parse::<Locale>().is_err());
+    }
+}
@@ -0,0 +1,460 @@
+//! Random timestamps for the `datetime`, `iso8601_datetime` and `date` methods.
+//!
+//! Arguments are positional, with `null` to keep a default:
+//! `[method, start, end, format, timezone]`. For example
+//! `["datetime", "-30d", "now", "unix_ms"]` or
+//! `["date", "2024-01-01", "2024-12-31", "%d/%m/%Y", "Europe/Paris"]`.
+
+use crate::error::{DataFakeError, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{
+    DateTime, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use rand::Rng;
+use serde_json::{Value, json};
+use std::cell::Cell;
+use std::fmt::Display;
+
+/// Range used when no start is given.
+const DEFAULT_START: &str = "-1y";
+const DEFAULT_END: &str = "now";
+
+/// Output formats besides strftime patterns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Format {
+    Rfc3339,
+    UnixSeconds,
+    UnixMillis,
+    Strftime(String),
+}
+
+impl Format {
+    /// Parses a format name or strftime pattern, rejecting unknown specifiers
+    /// that chrono would fail to render.
+    fn parse(format: &str) -> Result<Self> {
+        Ok(match format.to_ascii_lowercase().as_str() {
+            "rfc3339" | "iso8601" => Format::Rfc3339,
+            "unix" | "unix_s" | "timestamp" => Format::UnixSeconds,
+            "unix_ms" | "timestamp_ms" => Format::UnixMillis,
+            _ => {
+                if StrftimeItems::new(format).any(|item| item == Item::Error) {
+                    return Err(invalid(format!("invalid strftime format '{format}'")));
+                }
+                Format::Strftime(format.to_string())
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Zone {
+    Fixed(FixedOffset),
+    Named(Tz),
+}
+
+impl Zone {
+    fn parse(zone: &str) -> Result<Self> {
+        if zone.eq_ignore_ascii_case("utc") || zone == "Z" {
+            return Ok(Zone::Fixed(Utc.fix()));
+        }
+        if let Ok(offset) = zone.parse::<FixedOffset>() {
+            return Ok(Zone::Fixed(offset));
+        }
+        zone.parse::<Tz>()
+            .map(Zone::Named)
+            .map_err(|_| invalid(format!("unknown timezone '{zone}'")))
+    }
+
+    /// Interprets a wall-clock time in this zone, taking the earlier instant when
+    /// a DST change makes it ambiguous.
+    fn local_to_utc(&self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        fn resolve<Z: TimeZone>(result: LocalResult<DateTime<Z>>) -> Option<DateTime<Utc>> {
+            result.earliest().map(|dt| dt.with_timezone(&Utc))
+        }
+        match self {
+            Zone::Fixed(offset) => resolve(offset.from_local_datetime(&naive)),
+            Zone::Named(tz) => resolve(tz.from_local_datetime(&naive)),
+        }
+    }
+
+    fn today(&self, now: DateTime<Utc>) -> NaiveDate {
+        match self {
+            Zone::Fixed(offset) => now.with_timezone(offset).date_naive(),
+            Zone::Named(tz) => now.with_timezone(tz).date_naive(),
+        }
+    }
+
+    fn render(&self, instant: DateTime<Utc>, format: &Format) -> Value {
+        fn render_in<Z: TimeZone>(instant: DateTime<Utc>, zone: &Z, format: &Format) -> Value
+        where
+            Z::Offset: Display,
+        {
+            let local = instant.with_timezone(zone);
+            match format {
+                Format::Rfc3339 => Value::String(local.to_rfc3339()),
+                Format::UnixSeconds => Value::from(instant.timestamp()),
+                Format::UnixMillis => Value::from(instant.timestamp_millis()),
+                Format::Strftime(pattern) => Value::String(local.format(pattern).to_string()),
+            }
+        }
+        match self {
+            Zone::Fixed(offset) => render_in(instant, offset, format),
+            Zone::Named(tz) => render_in(instant, tz, format),
+        }
+    }
+}
+
+fn invalid(message: String) -> DataFakeError {
+    DataFakeError::FakeOperatorError(format!("Invalid datetime argument: {message}"))
+}
+
+// Like the RNG, the anchor has to reach `fake` calls that datalogic-rs evaluates
+// inside other operators, so the plan installs it in a thread local for the
+// duration of a generation.
+thread_local! {
+    static ACTIVE_NOW: Cell<Option<DateTime<Utc>>> = const { Cell::new(None) };
+}
+
+/// Runs `f` with `now` as the instant that `now`, `today` and relative bounds
+/// are resolved against.
+pub(crate) fn with_now<T>(now: DateTime<Utc>, f: impl FnOnce() -> T) -> T {
+    let previous = ACTIVE_NOW.with(|cell| cell.replace(Some(now)));
+    let result = f();
+    ACTIVE_NOW.with(|cell| cell.set(previous));
+    result
+}
+
+/// The anchor of the current generation, or the wall clock outside of one.
+fn now() -> DateTime<Utc> {
+    ACTIVE_NOW.with(Cell::get).unwrap_or_else(Utc::now)
+}
+
+/// Unix timestamp that seeded generations without a `metadata.now` resolve
+/// `now`, `today` and relative bounds against: 2025-01-01T00:00:00Z. A fixed
+/// instant keeps seeded output identical from one day to the next.
+pub const SEEDED_EPOCH: i64 = 1_735_689_600;
+
+/// The anchor of seeded generations without a `metadata.now`.
+pub(crate) fn seeded_now() -> DateTime<Utc> {
+    DateTime::from_timestamp(SEEDED_EPOCH, 0).expect("SEEDED_EPOCH is a valid timestamp")
+}
+
+/// Parses `metadata.now`, which must be an absolute date, datetime or unix epoch.
+pub(crate) fn parse_anchor(text: &str) -> Result<DateTime<Utc>> {
+    match Bound::parse(&Value::from(text), &Zone::Fixed(Utc.fix())) {
+        Ok(Bound::Instant(instant)) => Ok(instant),
+        _ => Err(DataFakeError::InvalidConfig(format!(
+            "now must be an absolute date, datetime or unix timestamp, got '{text}'"
+        ))),
+    }
+}
+
+/// A range bound, resolved against the generation's anchor instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    Instant(DateTime<Utc>),
+    /// `now` plus an offset (zero for `now` itself)
+    FromNow(Duration),
+    /// Midnight of the current day in the range's timezone
+    Today,
+}
+
+impl Bound {
+    /// Parses a unix epoch in seconds, `now`/`today`, a relative offset such as
+    /// `-30d` (units `s`, `m`, `h`, `d`, `w`, `y`), or an ISO 8601 date or
+    /// datetime. Dates and datetimes without an offset are read in `zone`.
+    fn parse(value: &Value, zone: &Zone) -> Result<Self> {
+        let text = match value {
+            Value::Number(n) => {
+                return n
+                    .as_i64()
+                    .and_then(|secs| DateTime::from_timestamp(secs, 0))
+                    .map(Bound::Instant)
+                    .ok_or_else(|| invalid(format!("unix timestamp {n} is out of range")));
+            }
+            Value::String(s) => s.trim(),
+            other => {
+                return Err(invalid(format!(
+                    "expected a date string or number, got {other}"
+                )));
+            }
+        };
+
+        match text.to_ascii_lowercase().as_str() {
+            "now" => return Ok(Bound::FromNow(Duration::zero())),
+            "today" => return Ok(Bound::Today),
+            _ => {}
+        }
+        if let Some(offset) = parse_relative(text) {
+            return Ok(Bound::FromNow(offset));
+        }
+        if let Ok(secs) = text.parse::<i64>() {
+            return DateTime::from_timestamp(secs, 0)
+                .map(Bound::Instant)
+                .ok_or_else(|| invalid(format!("unix timestamp {secs} is out of range")));
+        }
+        if let Ok(dt) = DateTime::parse_from_rfc3339(text) {
+            return Ok(Bound::Instant(dt.with_timezone(&Utc)));
+        }
+
+        let naive = NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f")
+            .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f"))
+            .or_else(|_| {
+                NaiveDate::parse_from_str(text, "%Y-%m-%d").map(|d| d.and_time(NaiveTime::MIN))
+            })
+            .map_err(|_| invalid(format!("cannot parse '{text}' as a date")))?;
+        zone.local_to_utc(naive)
+            .map(Bound::Instant)
+            .ok_or_else(|| invalid(format!("'{text}' does not exist in the timezone")))
+    }
+
+    fn resolve(self, now: DateTime<Utc>, zone: &Zone) -> Result<DateTime<Utc>> {
+        match self {
+            Bound::Instant(instant) => Ok(instant),
+            Bound::FromNow(offset) => now
+                .checked_add_signed(offset)
+                .ok_or_else(|| invalid(format!("now {offset} is out of range"))),
+            Bound::Today => {
+                let midnight = zone.today(now).and_time(NaiveTime::MIN);
+                zone.local_to_utc(midnight)
+                    .ok_or_else(|| invalid("today does not exist in the timezone".to_string()))
+            }
+        }
+    }
+}
+
+/// Parsed arguments of a datetime method, compiled once per plan.
+#[derive(Debug, Clone)]
+pub(crate) struct DateTimeRange {
+    start: Bound,
+    end: Bound,
+    format: Format,
+    zone: Zone,
+}
+
+impl DateTimeRange {
+    /// Parses `args` (including the method name).
+    pub(crate) fn parse(args: &[Value]) -> Result<Self> {
+        let method = args.first().and_then(Value::as_str).unwrap_or("datetime");
+        let mut rest: Vec<Option<&Value>> = args[1.min(args.len())..]
+            .iter()
+            .map(|v| (!v.is_null()).then_some(v))
+            .collect();
+
+        // `["date", "%Y-%m-%d"]` predates ranges and passes only a format
+        if rest.len() == 1
+            && let Some(Value::String(format)) = rest[0]
+            && format.contains('%')
+        {
+            rest.insert(0, None);
+            rest.insert(0, None);
+        }
+        let arg = |i: usize| rest.get(i).copied().flatten();
+
+        let zone = match arg(3) {
+            Some(Value::String(zone)) => Zone::parse(zone)?,
+            Some(other) => return Err(invalid(format!("timezone must be a string, got {other}"))),
+            None => Zone::Fixed(Utc.fix()),
+        };
+        let format = match arg(2) {
+            Some(Value::String(format)) => Format::parse(format)?,
+            Some(other) => return Err(invalid(format!("format must be a string, got {other}"))),
+            None if method == "date" => Format::Strftime("%Y-%m-%d".to_string()),
+            None => Format::Rfc3339,
+        };
+        let start = Bound::parse(arg(0).unwrap_or(&Value::from(DEFAULT_START)), &zone)?;
+        let end = Bound::parse(arg(1).unwrap_or(&Value::from(DEFAULT_END)), &zone)?;
+
+        let range = Self {
+            start,
+            end,
+            format,
+            zone,
+        };
+        // Bounds of the same kind are ordered whatever the anchor; mixed ones
+        // are checked when they are resolved
+        if matches!(
+            (start, end),
+            (Bound::Instant(_), Bound::Instant(_)) | (Bound::FromNow(_), Bound::FromNow(_))
+        ) {
+            range.resolve(Utc::now())?;
+        }
+        Ok(range)
+    }
+
+    fn resolve(&self, now: DateTime<Utc>) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+        let start = self.start.resolve(now, &self.zone)?;
+        let end = self.end.resolve(now, &self.zone)?;
+        if start > end {
+            return Err(invalid(format!(
+                "start {} is after end {}",
+                start.to_rfc3339(),
+                end.to_rfc3339()
+            )));
+        }
+        Ok((start, end))
+    }
+
+    /// Generates a random timestamp within the range, resolved against the
+    /// anchor of the current generation.
+    pub(crate) fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Value> {
+        let (start, end) = self.resolve(now())?;
+        let millis = rng.random_range(start.timestamp_millis()..=end.timestamp_millis());
+        let instant = DateTime::from_timestamp_millis(millis).unwrap_or(start);
+        Ok(self.zone.render(instant, &self.format))
+    }
+}
+
+/// Parses `[+-]<n><unit>`, e.g. `-30d` or `+2h`.
+fn parse_relative(text: &str) -> Option<Duration> {
+    let (sign, rest) = match text.as_bytes().first()? {
+        b'-' => (-1, &text[1..]),
+        b'+' => (1, &text[1..]),
+        _ => (1, text),
+    };
+    let unit_start = rest.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = rest.split_at(unit_start);
+    let amount: i64 = amount.parse().ok()?;
+    let duration = match unit {
+        "s" => Duration::try_seconds(amount),
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        "y" => Duration::try_days(amount.checked_mul(365)?),
+        _ => None,
+    }?;
+    Some(duration * sign)
+}
+
+/// Generates a random timestamp for `args` (including the method name).
+pub fn generate<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
+    DateTimeRange::parse(args)?.generate(rng)
+}
+
+/// JSON Schema of the values `args` generate: RFC 3339 strings are
+/// `date-time`s, the default `date` format is a `date`, and unix epochs are
+/// integers.
+pub fn json_schema(args: &[Value]) -> Value {
+    match DateTimeRange::parse(args).map(|range| range.format) {
+        Ok(Format::Rfc3339) => json!({"type": "string", "format": "date-time"}),
+        Ok(Format::UnixSeconds | Format::UnixMillis) => json!({"type": "integer"}),
+        Ok(Format::Strftime(format)) if format == "%Y-%m-%d" => {
+            json!({"type": "string", "format": "date"})
+        }
+        _ => json!({"type": "string"}),
+    }
+}
+
+/// Checks datetime arguments without generating anything, for config validation.
+pub fn validate(args: &[Value]) -> Result<()> {
+    DateTimeRange::parse(args).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use serde_json::json;
+
+    fn sample(args: Value) -> Vec<Value> {
+        let args = args.as_array().unwrap().clone();
+        let mut rng = StdRng::seed_from_u64(4);
+        (0..100)
+            .map(|_| generate(&args, &mut rng).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_absolute_range() {
+        let values = sample(json!(["datetime", "2024-01-01", "2024-01-31T23:59:59Z"]));
+        for value in &values {
+            let dt = DateTime::parse_from_rfc3339(value.as_str().unwrap()).unwrap();
+            assert!(dt.to_rfc3339().starts_with("2024-01-"), "{dt}");
+        }
+        // Records no longer share one timestamp
+        assert_ne!(values[0], values[1]);
+
+        for value in sample(json!(["datetime", 1_700_000_000, 1_700_000_060, "unix"])) {
+            assert!((1_700_000_000..=1_700_000_060).contains(&value.as_i64().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_relative_range() {
+        let now = Utc::now();
+        for value in sample(json!(["datetime", "-30d", "now", "unix_ms"])) {
+            let millis = value.as_i64().unwrap();
+            assert!(millis >= (now - Duration::days(30)).timestamp_millis());
+            assert!(millis <= Utc::now().timestamp_millis());
+        }
+        // Inside a generation, bounds are resolved against its anchor
+        let anchor = DateTime::parse_from_rfc3339("2024-05-01T08:00:00Z").unwrap();
+        let args = [json!("datetime"), json!("now"), json!("now")];
+        let value = with_now(anchor.to_utc(), || generate(&args, &mut rand::rng())).unwrap();
+        assert_eq!(value, "2024-05-01T08:00:00+00:00");
+
+        assert_eq!(parse_relative("+2h"), Some(Duration::hours(2)));
+        assert_eq!(parse_relative("1w"), Some(Duration::weeks(1)));
+        assert_eq!(parse_relative("-3x"), None);
+    }
+
+    #[test]
+    fn test_formats_and_timezones() {
+        for value in sample(json!(["date", "2024-03-01", "2024-03-31", "%d/%m/%Y"])) {
+            assert!(value.as_str().unwrap().ends_with("/03/2024"));
+        }
+
+        // Legacy `["date", format]` form
+        let legacy = sample(json!(["date", "%Y"]));
+        assert!(legacy.iter().all(|v| v.as_str().unwrap().len() == 4));
+
+        // Default date format
+        let value = &sample(json!(["date", "2024-06-15", "2024-06-15"]))[0];
+        assert_eq!(value, "2024-06-15");
+
+        // Naive bounds are read in the timezone, and output is rendered in it
+        let values = sample(json!([
+            "datetime",
+            "2024-07-01T09:00:00",
+            "2024-07-01T17:00:00",
+            null,
+            "America/New_York"
+        ]));
+        for value in values {
+            let text = value.as_str().unwrap();
+            assert!(text.ends_with("-04:00"), "{text}");
+            let hour: u32 = text[11..13].parse().unwrap();
+            assert!((9..=17).contains(&hour));
+        }
+
+        let value = &sample(json!([
+            "datetime",
+            "2024-01-01T00:00:00Z",
+            "2024-01-01T00:00:00Z",
+            null,
+            "+05:30"
+        ]))[0];
+        assert_eq!(value, "2024-01-01T05:30:00+05:30");
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        for args in [
+            json!(["datetime", "2024-02-01", "2024-01-01"]),
+            json!(["datetime", "yesterday-ish"]),
+            json!(["datetime", null, null, null, "Mars/Olympus"]),
+            json!(["datetime", true]),
+            json!(["datetime", null, null, "%Y-%Q"]),
+        ] {
+            assert!(validate(args.as_array().unwrap()).is_err(), "{args}");
+        }
+        assert!(validate(&[json!("datetime")]).is_ok());
+
+        assert!(parse_anchor("2024-01-01").is_ok());
+        assert!(parse_anchor("-1d").is_err());
+    }
+}
@@ -0,0 +1,400 @@
+//! Non-uniform sampling for the numeric fake methods (`u8` … `f64`).
+//!
+//! An options object can follow the optional `min, max` bounds:
+//! `["f64", 0, 500, {"distribution": "normal", "mean": 120, "std_dev": 30, "decimals": 2}]`.
+//! With bounds, samples are clamped to them; integer methods round to the
+//! nearest integer and are always clamped to the type's range.
+
+use crate::error::{DataFakeError, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::{Distribution as _, Exp, LogNormal, Normal, Pareto, Poisson, Zipf};
+use serde_json::{Map, Number, Value};
+
+/// A distribution with its parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    /// Uniform between the method's bounds (the default)
+    Uniform,
+    Normal {
+        mean: f64,
+        std_dev: f64,
+    },
+    /// `mu` and `sigma` of the underlying normal distribution
+    LogNormal {
+        mu: f64,
+        sigma: f64,
+    },
+    Exponential {
+        lambda: f64,
+    },
+    Poisson {
+        lambda: f64,
+    },
+    /// Ranks `1..=n` with exponent `s`
+    Zipf {
+        n: f64,
+        s: f64,
+    },
+    Pareto {
+        scale: f64,
+        shape: f64,
+    },
+}
+
+/// Parsed options object of a numeric method.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericOptions {
+    pub distribution: Distribution,
+    /// Decimal places floats are rounded to
+    pub decimals: Option<u32>,
+}
+
+impl NumericOptions {
+    pub fn parse(options: &Value) -> Result<Self> {
+        let Value::Object(options) = options else {
+            return Err(invalid(format!("options must be an object, got {options}")));
+        };
+
+        let name = match options.get("distribution") {
+            None => "uniform",
+            Some(Value::String(name)) => name.as_str(),
+            Some(other) => {
+                return Err(invalid(format!(
+                    "distribution must be a string, got {other}"
+                )));
+            }
+        };
+        let param = |key: &str| param(options, name, key);
+        let distribution = match name {
+            "uniform" => Distribution::Uniform,
+            "normal" | "gaussian" => Distribution::Normal {
+                mean: param("mean")?,
+                std_dev: non_negative("std_dev", param("std_dev")?)?,
+            },
+            "log_normal" | "lognormal" => Distribution::LogNormal {
+                mu: param("mu")?,
+                sigma: non_negative("sigma", param("sigma")?)?,
+            },
+            "exponential" => Distribution::Exponential {
+                lambda: param("lambda")?,
+            },
+            "poisson" => Distribution::Poisson {
+                lambda: param("lambda")?,
+            },
+            "zipf" => Distribution::Zipf {
+                n: param("n")?,
+                s: param("s")?,
+            },
+            "pareto" => Distribution::Pareto {
+                scale: param("scale")?,
+                shape: param("shape")?,
+            },
+            other => return Err(invalid(format!("unknown distribution '{other}'"))),
+        };
+
+        let decimals =
+            match options.get("decimals") {
+                None => None,
+                Some(value) => Some(value.as_u64().filter(|d| *d <= 15).ok_or_else(|| {
+                    invalid(format!("decimals must be an integer 0-15, got {value}"))
+                })? as u32),
+            };
+
+        let parsed = Self {
+            distribution,
+            decimals,
+        };
+        // Reject parameters the distribution itself refuses, e.g. a negative std_dev
+        parsed
+            .distribution
+            .sample(&mut StdRng::seed_from_u64(0), None)?;
+        Ok(parsed)
+    }
+}
+
+fn param(options: &Map<String, Value>, distribution: &str, key: &str) -> Result<f64> {
+    match options.get(key) {
+        Some(value) => value
+            .as_f64()
+            .ok_or_else(|| invalid(format!("{distribution} parameter '{key}' must be a number"))),
+        None => Err(invalid(format!(
+            "{distribution} requires a '{key}' parameter"
+        ))),
+    }
+}
+
+// rand_distr accepts a negative spread and mirrors the distribution, which is
+// never what a config author meant
+fn non_negative(key: &str, value: f64) -> Result<f64> {
+    if value < 0.0 {
+        return Err(invalid(format!(
+            "'{key}' must not be negative, got {value}"
+        )));
+    }
+    Ok(value)
+}
+
+fn invalid(message: String) -> DataFakeError {
+    DataFakeError::FakeOperatorError(format!("Invalid numeric options: {message}"))
+}
+
+impl Distribution {
+    /// Draws a sample. Uniform sampling needs `bounds`; the other distributions
+    /// ignore them (callers clamp).
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R, bounds: Option<(f64, f64)>) -> Result<f64> {
+        let error = |e: &dyn std::fmt::Display| invalid(e.to_string());
+        Ok(match *self {
+            Distribution::Uniform => match bounds {
+                Some((min, max)) => rng.random_range(min..=max),
+                None => rng.random(),
+            },
+            Distribution::Normal { mean, std_dev } => Normal::new(mean, std_dev)
+                .map_err(|e| error(&e))?
+                .sample(rng),
+            Distribution::LogNormal { mu, sigma } => LogNormal::new(mu, sigma)
+                .map_err(|e| error(&e))?
+                .sample(rng),
+            Distribution::Exponential { lambda } => {
+                Exp::new(lambda).map_err(|e| error(&e))?.sample(rng)
+            }
+            Distribution::Poisson { lambda } => {
+                Poisson::new(lambda).map_err(|e| error(&e))?.sample(rng)
+            }
+            Distribution::Zipf { n, s } => Zipf::new(n, s).map_err(|e| error(&e))?.sample(rng),
+            Distribution::Pareto { scale, shape } => Pareto::new(scale, shape)
+                .map_err(|e| error(&e))?
+                .sample(rng),
+        })
+    }
+}
+
+/// Value range of a numeric method, and whether it produces integers.
+fn type_range(method: &str) -> Option<(f64, f64, bool)> {
+    Some(match method {
+        "u8" => (0.0, u8::MAX as f64, true),
+        "u16" => (0.0, u16::MAX as f64, true),
+        "u32" => (0.0, u32::MAX as f64, true),
+        "u64" => (0.0, u64::MAX as f64, true),
+        "i8" => (i8::MIN as f64, i8::MAX as f64, true),
+        "i16" => (i16::MIN as f64, i16::MAX as f64, true),
+        "i32" => (i32::MIN as f64, i32::MAX as f64, true),
+        "i64" => (i64::MIN as f64, i64::MAX as f64, true),
+        "f32" => (f32::MIN as f64, f32::MAX as f64, false),
+        "f64" => (f64::MIN, f64::MAX, false),
+        _ => return None,
+    })
+}
+
+/// A parsed `[method, (min, max,) options]` call.
+struct NumericCall<'a> {
+    method: &'a str,
+    integer: bool,
+    options: NumericOptions,
+    min: f64,
+    max: f64,
+}
+
+impl<'a> NumericCall<'a> {
+    fn parse(args: &'a [Value]) -> Result<Self> {
+        let method = args.first().and_then(Value::as_str).unwrap_or_default();
+        let (type_min, type_max, integer) = type_range(method)
+            .ok_or_else(|| invalid(format!("'{method}' is not a numeric method")))?;
+        let (options, bounds) = match args {
+            [_, options] => (options, None),
+            [_, min, max, options] => {
+                let bound = |v: &Value| {
+                    v.as_f64()
+                        .ok_or_else(|| invalid(format!("bounds must be numbers, got {v}")))
+                };
+                (options, Some((bound(min)?, bound(max)?)))
+            }
+            _ => {
+                return Err(DataFakeError::FakeOperatorError(format!(
+                    "{method} requires [options] or [min, max, options] with a distribution"
+                )));
+            }
+        };
+        let options = NumericOptions::parse(options)?;
+
+        let bounds = bounds.unwrap_or(
+            if integer || options.distribution != Distribution::Uniform {
+                (type_min, type_max)
+            } else {
+                (0.0, 1.0)
+            },
+        );
+        if bounds.0 > bounds.1 {
+            return Err(DataFakeError::InvalidRange {
+                min: bounds.0,
+                max: bounds.1,
+            });
+        }
+        let (min, max) = (bounds.0.max(type_min), bounds.1.min(type_max));
+        // Bounds outside the type's range leave nothing to clamp to, and uniform
+        // sampling needs a span that fits in an f64
+        let uniform = options.distribution == Distribution::Uniform;
+        if min > max || (uniform && !(max - min).is_finite()) {
+            return Err(DataFakeError::InvalidRange { min, max });
+        }
+
+        Ok(Self {
+            method,
+            integer,
+            options,
+            min,
+            max,
+        })
+    }
+}
+
+/// Checks a numeric method call with an options object without sampling it.
+pub fn validate(args: &[Value]) -> Result<()> {
+    NumericCall::parse(args).map(|_| ())
+}
+
+/// Generates a numeric method's value from `[method, (min, max,) options]`.
+pub fn generate<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
+    let NumericCall {
+        method,
+        integer,
+        options,
+        min,
+        max,
+    } = NumericCall::parse(args)?;
+
+    let mut value = options.distribution.sample(rng, Some((min, max)))?;
+    if integer {
+        value = value.round();
+    } else if let Some(decimals) = options.decimals {
+        let factor = 10f64.powi(decimals as i32);
+        value = (value * factor).round() / factor;
+    }
+    // Rounding can step just past a bound, so clamp last
+    let value = value.clamp(min, max);
+
+    Ok(match method {
+        "u8" | "u16" | "u32" | "u64" => Value::from(value as u64),
+        "i8" | "i16" | "i32" | "i64" => Value::from(value as i64),
+        // Narrowing a rounded value to f32 would bring back digits past `decimals`
+        "f32" if options.decimals.is_none() => {
+            Number::from_f64(value as f32 as f64).map_or(Value::Null, Value::Number)
+        }
+        _ => Number::from_f64(value).map_or(Value::Null, Value::Number),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn samples(args: Value) -> Vec<f64> {
+        let args = args.as_array().unwrap().clone();
+        let mut rng = StdRng::seed_from_u64(8);
+        (0..2000)
+            .map(|_| generate(&args, &mut rng).unwrap().as_f64().unwrap())
+            .collect()
+    }
+
+    fn mean(values: &[f64]) -> f64 {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+
+    #[test]
+    fn test_normal_with_clamping_and_decimals() {
+        let values = samples(json!([
+            "f64", 0, 150,
+            {"distribution": "normal", "mean": 100, "std_dev": 20, "decimals": 2}
+        ]));
+        assert!((mean(&values) - 100.0).abs() < 3.0);
+        assert!(values.iter().all(|v| (0.0..=150.0).contains(v)));
+        assert!(values.contains(&150.0)); // clamped tail
+        for v in &values {
+            assert!(((v * 100.0).round() - v * 100.0).abs() < 1e-6, "{v}");
+        }
+    }
+
+    #[test]
+    fn test_integer_distributions() {
+        let poisson = samples(json!(["u32", {"distribution": "poisson", "lambda": 4}]));
+        assert!((mean(&poisson) - 4.0).abs() < 0.3);
+        assert!(poisson.iter().all(|v| v.fract() == 0.0 && *v >= 0.0));
+
+        let zipf = samples(json!(["u16", {"distribution": "zipf", "n": 100, "s": 1.2}]));
+        let ones = zipf.iter().filter(|v| **v == 1.0).count();
+        assert!(ones > zipf.iter().filter(|v| **v == 2.0).count());
+        assert!(zipf.iter().all(|v| (1.0..=100.0).contains(v)));
+
+        // Negative normal samples are clamped to the unsigned range
+        let small = samples(json!(["u8", {"distribution": "normal", "mean": 0, "std_dev": 5}]));
+        assert!(small.iter().all(|v| (0.0..=255.0).contains(v)));
+    }
+
+    #[test]
+    fn test_skewed_distributions() {
+        let exponential = samples(json!(["f64", {"distribution": "exponential", "lambda": 2}]));
+        assert!((mean(&exponential) - 0.5).abs() < 0.05);
+
+        let pareto = samples(json!(["f64", {"distribution": "pareto", "scale": 1, "shape": 3}]));
+        assert!(pareto.iter().all(|v| *v >= 1.0));
+
+        let log_normal = samples(json!([
+            "f64", 0, 1000,
+            {"distribution": "log_normal", "mu": 3, "sigma": 0.5, "decimals": 0}
+        ]));
+        assert!(log_normal.iter().all(|v| v.fract() == 0.0));
+
+        // Uniform floats can still be rounded
+        let prices = samples(json!(["f64", 1, 100, {"decimals": 2}]));
+        assert!(prices.iter().all(|v| (1.0..=100.0).contains(v)));
+
+        let prices = samples(json!(["f32", 0, 100, {"decimals": 2}]));
+        for v in &prices {
+            assert!((0.0..=100.0).contains(v));
+            assert!(
+                v.to_string().split('.').nth(1).is_none_or(|d| d.len() <= 2),
+                "{v}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unsatisfiable_bounds() {
+        let mut rng = StdRng::seed_from_u64(8);
+        for args in [
+            // Entirely above the u8 range
+            json!(["u8", 300, 400, {"distribution": "normal", "mean": 0, "std_dev": 1}]),
+            // The span overflows to infinity
+            json!(["f64", -1e308, 1e308, {"decimals": 2}]),
+        ] {
+            let args = args.as_array().unwrap();
+            assert!(matches!(
+                validate(args),
+                Err(DataFakeError::InvalidRange { .. })
+            ));
+            assert!(matches!(
+                generate(args, &mut rng),
+                Err(DataFakeError::InvalidRange { .. })
+            ));
+        }
+
+        // Only uniform sampling needs a finite span
+        let args = json!(["f64", {"distribution": "normal", "mean": 0, "std_dev": 1}]);
+        assert!(generate(args.as_array().unwrap(), &mut rng).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        for options in [
+            json!({"distribution": "normal", "mean": 0}),
+            json!({"distribution": "normal", "mean": 0, "std_dev": -1}),
+            json!({"distribution": "poisson", "lambda": 0}),
+            json!({"distribution": "cauchy"}),
+            json!({"decimals": -1}),
+            json!("normal"),
+        ] {
+            assert!(NumericOptions::parse(&options).is_err(), "{options}");
+        }
+    }
+}
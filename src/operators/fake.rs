@@ -1,28 +1,87 @@
 use crate::error::{DataFakeError, Result};
-use chrono::Utc;
+use crate::locale::Locale;
+use crate::operators::{RegexGenerator, datetime, distribution, finance};
+use crate::rng;
 use datalogic_rs::{ContextStack, Evaluator, Operator};
-use fake::faker::address::en::{
-    CityName, CountryCode, CountryName, Latitude, Longitude, PostCode, StateAbbr, StateName,
-    StreetName, StreetSuffix, ZipCode,
-};
+use fake::faker::address::en::{Latitude, Longitude};
 use fake::faker::barcode::en::{Isbn10, Isbn13};
-use fake::faker::company::en::{
-    Bs, BsAdj, BsNoun, BsVerb, CatchPhrase, CompanyName, CompanySuffix, Industry, Profession,
-};
 use fake::faker::creditcard::en::CreditCardNumber;
 use fake::faker::currency::en::{CurrencyCode, CurrencyName, CurrencySymbol};
 use fake::faker::filesystem::en::{DirPath, FileExtension, FileName, FilePath};
 use fake::faker::finance::en::Bic;
-use fake::faker::internet::en::{
-    DomainSuffix, FreeEmail, IPv4, IPv6, MACAddress, Password, SafeEmail, UserAgent, Username,
-};
+use fake::faker::impls::address::CityNameGenFn;
+use fake::faker::internet::en::{IPv4, IPv6, MACAddress, Password, UserAgent};
 use fake::faker::lorem::en::{Paragraph, Sentence, Word, Words};
-use fake::faker::name::en::{FirstName, LastName, NameWithTitle, Suffix, Title};
-use fake::faker::phone_number::en::{CellNumber, PhoneNumber};
+use fake::locales::{AR_SA, CY_GB, DE_DE, EN, FR_FR, IT_IT, JA_JP, PT_BR, PT_PT, ZH_CN, ZH_TW};
 use fake::{Fake, Faker};
 use rand::Rng;
 use serde_json::Value;
 
+/// Numeric methods, which take optional `min, max` bounds and a trailing
+/// distribution options object (see [`distribution`]).
+pub const NUMERIC_METHODS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64", "f32", "f64",
+];
+
+/// Values of the `month_name` method.
+pub const MONTH_NAMES: &[&str] = &[
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Methods whose output depends on the locale, passed as the second argument
+/// (e.g. `["name", "fr_FR"]`).
+pub const LOCALIZED_METHODS: &[&str] = &[
+    "street_address",
+    "city",
+    "city_name",
+    "country_name",
+    "country_code",
+    "state_name",
+    "state_abbr",
+    "zip_code",
+    "zip",
+    "post_code",
+    "postcode",
+    "postal_code",
+    "street_name",
+    "street_suffix",
+    "name",
+    "full_name",
+    "first_name",
+    "last_name",
+    "name_with_title",
+    "title",
+    "suffix",
+    "company_name",
+    "company_suffix",
+    "industry",
+    "profession",
+    "catch_phrase",
+    "bs",
+    "bs_adj",
+    "bs_noun",
+    "bs_verb",
+    "email",
+    "safe_email",
+    "free_email",
+    "username",
+    "domain_suffix",
+    "domain_name",
+    "phone_number",
+    "cell_number",
+];
+
 pub struct FakeOperator;
 
 impl Operator for FakeOperator {
@@ -38,7 +97,14 @@ impl Operator for FakeOperator {
 }
 
 impl FakeOperator {
+    /// Generates a value using the active RNG of the current generation (or the
+    /// thread RNG when called outside of one).
     pub fn generate(args: &[Value]) -> Result<Value> {
+        rng::with_rng(|rng| Self::generate_with_rng(args, rng))
+    }
+
+    /// Generates a value drawing all randomness from `rng`.
+    pub fn generate_with_rng<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
         if args.is_empty() {
             return Err(DataFakeError::FakeOperatorError(
                 "Fake operator requires at least one argument".to_string(),
@@ -49,209 +115,123 @@ impl FakeOperator {
             DataFakeError::FakeOperatorError("First argument must be a string".to_string())
         })?;
 
-        let _locale = args.get(1).and_then(|v| v.as_str()).unwrap_or("en");
+        if LOCALIZED_METHODS.contains(&method) {
+            let locale = match args.get(1) {
+                Some(Value::String(code)) => code.parse()?,
+                _ => Locale::En,
+            };
+            return Self::generate_localized(method, locale, rng);
+        }
+
+        if NUMERIC_METHODS.contains(&method) && args.last().is_some_and(Value::is_object) {
+            return distribution::generate(args, rng);
+        }
 
         match method {
             // Numeric types with optional range
-            "u8" => Self::generate_u8(args),
-            "u16" => Self::generate_u16(args),
-            "u32" => Self::generate_u32(args),
-            "u64" => Self::generate_u64(args),
-            "i8" => Self::generate_i8(args),
-            "i16" => Self::generate_i16(args),
-            "i32" => Self::generate_i32(args),
-            "i64" => Self::generate_i64(args),
-            "f32" => Self::generate_f32(args),
-            "f64" => Self::generate_f64(args),
+            "u8" => Self::generate_u8(args, rng),
+            "u16" => Self::generate_u16(args, rng),
+            "u32" => Self::generate_u32(args, rng),
+            "u64" => Self::generate_u64(args, rng),
+            "i8" => Self::generate_i8(args, rng),
+            "i16" => Self::generate_i16(args, rng),
+            "i32" => Self::generate_i32(args, rng),
+            "i64" => Self::generate_i64(args, rng),
+            "f32" => Self::generate_f32(args, rng),
+            "f64" => Self::generate_f64(args, rng),
 
             // Boolean
-            "bool" | "boolean" => Ok(Value::Bool(rand::rng().random())),
+            "bool" | "boolean" => Ok(Value::Bool(rng.random())),
 
             // UUID
-            "uuid" => Ok(Value::String(fake::uuid::UUIDv4.fake())),
+            "uuid" => Ok(Value::String(fake::uuid::UUIDv4.fake_with_rng(rng))),
 
             // Address related
-            "street_address" => {
-                // Generate a street address by combining components
-                let street_num: u16 = (1..9999).fake();
-                let street = StreetName().fake::<String>();
-                let suffix = StreetSuffix().fake::<String>();
-                Ok(Value::String(format!("{street_num} {street} {suffix}")))
-            }
-            "city" | "city_name" => Ok(Value::String(CityName().fake())),
-            "country_name" => Ok(Value::String(CountryName().fake())),
-            "country_code" => Ok(Value::String(CountryCode().fake())),
-            "state_name" => Ok(Value::String(StateName().fake())),
-            "state_abbr" => Ok(Value::String(StateAbbr().fake())),
-            "zip_code" | "zip" => Ok(Value::String(ZipCode().fake())),
-            "post_code" | "postcode" | "postal_code" => Ok(Value::String(PostCode().fake())),
             "latitude" => Ok(Value::Number(
-                serde_json::Number::from_f64(Latitude().fake::<f64>()).unwrap(),
+                serde_json::Number::from_f64(Latitude().fake_with_rng::<f64, _>(rng)).unwrap(),
             )),
             "longitude" => Ok(Value::Number(
-                serde_json::Number::from_f64(Longitude().fake::<f64>()).unwrap(),
+                serde_json::Number::from_f64(Longitude().fake_with_rng::<f64, _>(rng)).unwrap(),
             )),
-            "street_name" => Ok(Value::String(StreetName().fake())),
-            "street_suffix" => Ok(Value::String(StreetSuffix().fake())),
-
-            // Name related
-            "name" | "full_name" => {
-                // For now, use English locale only
-                use fake::faker::name::en::Name;
-                Ok(Value::String(Name().fake()))
-            }
-            "first_name" => Ok(Value::String(FirstName().fake())),
-            "last_name" => Ok(Value::String(LastName().fake())),
-            "name_with_title" => Ok(Value::String(NameWithTitle().fake())),
-            "title" => Ok(Value::String(Title().fake())),
-            "suffix" => Ok(Value::String(Suffix().fake())),
-
-            // Company related
-            "company_name" => Ok(Value::String(CompanyName().fake())),
-            "company_suffix" => Ok(Value::String(CompanySuffix().fake())),
-            "industry" => Ok(Value::String(Industry().fake())),
-            "profession" => Ok(Value::String(Profession().fake())),
-            "catch_phrase" => Ok(Value::String(CatchPhrase().fake())),
-            "bs" => Ok(Value::String(Bs().fake())),
-            "bs_adj" => Ok(Value::String(BsAdj().fake())),
-            "bs_noun" => Ok(Value::String(BsNoun().fake())),
-            "bs_verb" => Ok(Value::String(BsVerb().fake())),
 
             // Internet related
-            "email" | "safe_email" => Ok(Value::String(SafeEmail().fake())),
-            "free_email" => Ok(Value::String(FreeEmail().fake())),
-            "username" => Ok(Value::String(Username().fake())),
             "password" => {
                 let min_len = args.get(1).and_then(|v| v.as_u64()).unwrap_or(8) as usize;
                 let max_len = args.get(2).and_then(|v| v.as_u64()).unwrap_or(20) as usize;
-                Ok(Value::String(Password(min_len..max_len).fake()))
+                Ok(Value::String(Password(min_len..max_len).fake_with_rng(rng)))
             }
-            "domain_suffix" => Ok(Value::String(DomainSuffix().fake())),
-            "domain_name" => {
-                // Generate a domain name
-                let words: Vec<String> = Words(1..2).fake();
-                let suffix = DomainSuffix().fake::<String>();
-                Ok(Value::String(format!(
-                    "{}.{}",
-                    words.join("").to_lowercase(),
-                    suffix
-                )))
-            }
-            "ipv4" => Ok(Value::String(IPv4().fake())),
-            "ipv6" => Ok(Value::String(IPv6().fake())),
-            "mac_address" => Ok(Value::String(MACAddress().fake())),
-            "user_agent" => Ok(Value::String(UserAgent().fake())),
-
-            // Phone
-            "phone_number" => Ok(Value::String(PhoneNumber().fake())),
-            "cell_number" => Ok(Value::String(CellNumber().fake())),
+            "ipv4" => Ok(Value::String(IPv4().fake_with_rng(rng))),
+            "ipv6" => Ok(Value::String(IPv6().fake_with_rng(rng))),
+            "mac_address" => Ok(Value::String(MACAddress().fake_with_rng(rng))),
+            "user_agent" => Ok(Value::String(UserAgent().fake_with_rng(rng))),
 
             // Finance
-            "bic" => Ok(Value::String(Bic().fake())),
-            "credit_card_number" => Ok(Value::String(CreditCardNumber().fake())),
+            "bic" => Ok(Value::String(Bic().fake_with_rng(rng))),
+            "credit_card_number" => Ok(Value::String(CreditCardNumber().fake_with_rng(rng))),
 
             // Currency
-            "currency_code" => Ok(Value::String(CurrencyCode().fake())),
-            "currency_name" => Ok(Value::String(CurrencyName().fake())),
-            "currency_symbol" => Ok(Value::String(CurrencySymbol().fake())),
+            "currency_code" => Ok(Value::String(CurrencyCode().fake_with_rng(rng))),
+            "currency_name" => Ok(Value::String(CurrencyName().fake_with_rng(rng))),
+            "currency_symbol" => Ok(Value::String(CurrencySymbol().fake_with_rng(rng))),
 
             // Lorem
-            "word" => Ok(Value::String(Word().fake())),
+            "word" => Ok(Value::String(Word().fake_with_rng(rng))),
             "words" => {
                 let count = args.get(1).and_then(|v| v.as_u64()).unwrap_or(5) as usize;
-                let words: Vec<String> = Words(count..count + 1).fake();
+                let words: Vec<String> = Words(count..count + 1).fake_with_rng(rng);
                 Ok(Value::String(words.join(" ")))
             }
             "sentence" => {
                 let min_words = args.get(1).and_then(|v| v.as_u64()).unwrap_or(4) as usize;
                 let max_words = args.get(2).and_then(|v| v.as_u64()).unwrap_or(10) as usize;
-                Ok(Value::String(Sentence(min_words..max_words).fake()))
+                Ok(Value::String(
+                    Sentence(min_words..max_words).fake_with_rng(rng),
+                ))
             }
             "paragraph" => {
                 let min_sentences = args.get(1).and_then(|v| v.as_u64()).unwrap_or(3) as usize;
                 let max_sentences = args.get(2).and_then(|v| v.as_u64()).unwrap_or(7) as usize;
                 Ok(Value::String(
-                    Paragraph(min_sentences..max_sentences).fake(),
+                    Paragraph(min_sentences..max_sentences).fake_with_rng(rng),
                 ))
             }
 
             // Barcode
-            "isbn10" => Ok(Value::String(Isbn10().fake())),
-            "isbn13" => Ok(Value::String(Isbn13().fake())),
+            "isbn10" => Ok(Value::String(Isbn10().fake_with_rng(rng))),
+            "isbn13" => Ok(Value::String(Isbn13().fake_with_rng(rng))),
 
             // Filesystem
-            "file_name" => Ok(Value::String(FileName().fake())),
-            "file_extension" => Ok(Value::String(FileExtension().fake())),
-            "dir_path" => Ok(Value::String(DirPath().fake())),
-            "file_path" => Ok(Value::String(FilePath().fake())),
-
-            // Date/Time - Generate ISO8601 formatted datetime
-            "datetime" | "iso8601_datetime" => {
-                let now = Utc::now();
-                Ok(Value::String(now.to_rfc3339()))
-            }
-            "date" => {
-                // Generate date in specified format
-                let format = args.get(1).and_then(|v| v.as_str()).unwrap_or("%Y-%m-%d");
-                let now = Utc::now();
-                Ok(Value::String(now.format(format).to_string()))
-            }
+            "file_name" => Ok(Value::String(FileName().fake_with_rng(rng))),
+            "file_extension" => Ok(Value::String(FileExtension().fake_with_rng(rng))),
+            "dir_path" => Ok(Value::String(DirPath().fake_with_rng(rng))),
+            "file_path" => Ok(Value::String(FilePath().fake_with_rng(rng))),
+
+            // Date/Time - Random instants within a range
+            "datetime" | "iso8601_datetime" | "date" => datetime::generate(args, rng),
             "time" => {
                 // Generate time in HH:MM:SS format
-                let mut rng = rand::rng();
                 let hour = rng.random_range(0..24);
                 let minute = rng.random_range(0..60);
                 let second = rng.random_range(0..60);
                 Ok(Value::String(format!("{hour:02}:{minute:02}:{second:02}")))
             }
             "month_name" => {
-                // Generate a month name
-                let months = [
-                    "January",
-                    "February",
-                    "March",
-                    "April",
-                    "May",
-                    "June",
-                    "July",
-                    "August",
-                    "September",
-                    "October",
-                    "November",
-                    "December",
-                ];
-                let mut rng = rand::rng();
-                let idx = rng.random_range(0..months.len());
-                Ok(Value::String(months[idx].to_string()))
+                let idx = rng.random_range(0..MONTH_NAMES.len());
+                Ok(Value::String(MONTH_NAMES[idx].to_string()))
             }
 
             // Financial - Custom types for MX messages
             "iban" => {
                 let country = args.get(1).and_then(|v| v.as_str()).unwrap_or("DE");
-                let mut rng = rand::rng();
-                let check = format!("{:02}", rng.random_range(10..99));
-                let account: String = (0..18)
-                    .map(|_| rng.random_range(0..10).to_string())
-                    .collect();
-                Ok(Value::String(format!("{country}{check}{account}")))
-            }
-            "lei" => {
-                // Generate a realistic LEI (18 uppercase alphanumeric + 2 check digits)
-                let chars = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
-                let mut rng = rand::rng();
-                let lei: String = (0..18)
-                    .map(|_| chars.chars().nth(rng.random_range(0..chars.len())).unwrap())
-                    .collect();
-                let check = format!("{:02}", rng.random_range(10..99));
-                Ok(Value::String(format!("{lei}{check}")))
+                Ok(Value::String(finance::generate_iban(country, rng)?))
             }
+            "lei" => Ok(Value::String(finance::generate_lei(rng))),
             "alphanumeric" => {
                 let min_len = args.get(1).and_then(|v| v.as_u64()).unwrap_or(10) as usize;
                 let max_len = args
                     .get(2)
                     .and_then(|v| v.as_u64())
                     .unwrap_or(min_len as u64) as usize;
-                let mut rng = rand::rng();
                 let len = if min_len == max_len {
                     min_len
                 } else {
@@ -272,26 +252,34 @@ impl FakeOperator {
                     ));
                 }
                 let options = &args[1..];
-                let mut rng = rand::rng();
                 let idx = rng.random_range(0..options.len());
                 Ok(options[idx].clone())
             }
 
-            // Regex - Handle simple regex patterns for selecting from options
+            "weighted" | "weighted_choice" => {
+                let (values, weights) = Self::weighted_options(args)?;
+                let total: f64 = weights.iter().sum();
+                if !total.is_finite() {
+                    return Err(DataFakeError::FakeOperatorError(format!(
+                        "weighted weights must add up to a finite number, got {total}"
+                    )));
+                }
+                let mut target = rng.random_range(0.0..total);
+                for (value, weight) in values.iter().zip(&weights) {
+                    if target < *weight {
+                        return Ok((*value).clone());
+                    }
+                    target -= weight;
+                }
+                // Rounding can leave a sliver past the last positive weight
+                let last = weights.iter().rposition(|w| *w > 0.0).unwrap_or(0);
+                Ok(values[last].clone())
+            }
+
+            // Regex - Generate a string matching the pattern
             "regex" => {
                 if let Some(Value::String(pattern)) = args.get(1) {
-                    // Simple handling for common patterns like "(A|B|C)"
-                    if pattern.starts_with('(') && pattern.ends_with(')') {
-                        let inner = &pattern[1..pattern.len() - 1];
-                        let options: Vec<&str> = inner.split('|').collect();
-                        if !options.is_empty() {
-                            let mut rng = rand::rng();
-                            let idx = rng.random_range(0..options.len());
-                            return Ok(Value::String(options[idx].to_string()));
-                        }
-                    }
-                    // For other patterns, just return a placeholder
-                    Ok(Value::String("REGEX_PATTERN".to_string()))
+                    Ok(Value::String(RegexGenerator::parse(pattern)?.generate(rng)))
                 } else {
                     Err(DataFakeError::FakeOperatorError(
                         "regex requires a pattern argument".to_string(),
@@ -305,14 +293,149 @@ impl FakeOperator {
         }
     }
 
-    fn generate_u8(args: &[Value]) -> Result<Value> {
+    /// Splits the `[value, weight]` pairs of `weighted`, checking that weights are
+    /// non-negative numbers and that at least one is positive.
+    pub fn weighted_options(args: &[Value]) -> Result<(Vec<&Value>, Vec<f64>)> {
+        let pairs = &args[1.min(args.len())..];
+        if pairs.is_empty() {
+            return Err(DataFakeError::FakeOperatorError(
+                "weighted requires at least one [value, weight] pair".to_string(),
+            ));
+        }
+
+        let mut values = Vec::with_capacity(pairs.len());
+        let mut weights = Vec::with_capacity(pairs.len());
+        for pair in pairs {
+            match pair {
+                Value::Array(pair) if pair.len() == 2 => match pair[1].as_f64() {
+                    Some(weight) if weight >= 0.0 && weight.is_finite() => {
+                        values.push(&pair[0]);
+                        weights.push(weight);
+                    }
+                    _ => {
+                        return Err(DataFakeError::FakeOperatorError(format!(
+                            "weighted weights must be non-negative numbers, got {}",
+                            pair[1]
+                        )));
+                    }
+                },
+                other => {
+                    return Err(DataFakeError::FakeOperatorError(format!(
+                        "weighted options must be [value, weight] pairs, got {other}"
+                    )));
+                }
+            }
+        }
+        if !weights.iter().any(|w| *w > 0.0) {
+            return Err(DataFakeError::FakeOperatorError(
+                "weighted requires at least one positive weight".to_string(),
+            ));
+        }
+        Ok((values, weights))
+    }
+
+    fn generate_localized<R: Rng + ?Sized>(
+        method: &str,
+        locale: Locale,
+        rng: &mut R,
+    ) -> Result<Value> {
+        let value = match locale {
+            Locale::En => Self::localized_string(method, EN, rng),
+            Locale::FrFr => Self::localized_string(method, FR_FR, rng),
+            Locale::DeDe => Self::localized_string(method, DE_DE, rng),
+            Locale::ItIt => Self::localized_string(method, IT_IT, rng),
+            Locale::PtBr => Self::localized_string(method, PT_BR, rng),
+            Locale::PtPt => Self::localized_string(method, PT_PT, rng),
+            Locale::JaJp => Self::localized_string(method, JA_JP, rng),
+            Locale::ZhCn => Self::localized_string(method, ZH_CN, rng),
+            Locale::ZhTw => Self::localized_string(method, ZH_TW, rng),
+            Locale::ArSa => Self::localized_string(method, AR_SA, rng),
+            Locale::CyGb => Self::localized_string(method, CY_GB, rng),
+        };
+        value.map(Value::String).ok_or_else(|| {
+            DataFakeError::FakeOperatorError(format!("Unknown fake method: {method}"))
+        })
+    }
+
+    fn localized_string<L: CityNameGenFn, R: Rng + ?Sized>(
+        method: &str,
+        locale: L,
+        rng: &mut R,
+    ) -> Option<String> {
+        use fake::faker::address::raw::*;
+        use fake::faker::company::raw::*;
+        use fake::faker::internet::raw::*;
+        use fake::faker::name::raw::*;
+        use fake::faker::phone_number::raw::*;
+
+        let value = match method {
+            // Address related
+            "street_address" => {
+                // The locale's street template already places the street suffix
+                let street_num: u16 = (1..9999).fake_with_rng(rng);
+                let street = StreetName(locale).fake_with_rng::<String, _>(rng);
+                format!("{street_num} {street}")
+            }
+            "city" | "city_name" => CityName(locale).fake_with_rng(rng),
+            "country_name" => CountryName(locale).fake_with_rng(rng),
+            "country_code" => CountryCode(locale).fake_with_rng(rng),
+            "state_name" => StateName(locale).fake_with_rng(rng),
+            "state_abbr" => StateAbbr(locale).fake_with_rng(rng),
+            "zip_code" | "zip" => ZipCode(locale).fake_with_rng(rng),
+            "post_code" | "postcode" | "postal_code" => PostCode(locale).fake_with_rng(rng),
+            "street_name" => StreetName(locale).fake_with_rng(rng),
+            "street_suffix" => StreetSuffix(locale).fake_with_rng(rng),
+
+            // Name related
+            "name" | "full_name" => Name(locale).fake_with_rng(rng),
+            "first_name" => FirstName(locale).fake_with_rng(rng),
+            "last_name" => LastName(locale).fake_with_rng(rng),
+            "name_with_title" => NameWithTitle(locale).fake_with_rng(rng),
+            "title" => Title(locale).fake_with_rng(rng),
+            "suffix" => Suffix(locale).fake_with_rng(rng),
+
+            // Company related
+            "company_name" => CompanyName(locale).fake_with_rng(rng),
+            "company_suffix" => CompanySuffix(locale).fake_with_rng(rng),
+            "industry" => Industry(locale).fake_with_rng(rng),
+            "profession" => Profession(locale).fake_with_rng(rng),
+            "catch_phrase" => CatchPhrase(locale).fake_with_rng(rng),
+            "bs" => Bs(locale).fake_with_rng(rng),
+            "bs_adj" => BsAdj(locale).fake_with_rng(rng),
+            "bs_noun" => BsNoun(locale).fake_with_rng(rng),
+            "bs_verb" => BsVerb(locale).fake_with_rng(rng),
+
+            // Internet related
+            "email" | "safe_email" => SafeEmail(locale).fake_with_rng(rng),
+            "free_email" => FreeEmail(locale).fake_with_rng(rng),
+            "username" => Username(locale).fake_with_rng(rng),
+            "domain_suffix" => DomainSuffix(locale).fake_with_rng(rng),
+            "domain_name" => {
+                // Domain labels come from English words in every locale
+                let words: Vec<String> = Words(1..2).fake_with_rng(rng);
+                let suffix = DomainSuffix(locale).fake_with_rng::<String, _>(rng);
+                format!("{}.{}", words.join("").to_lowercase(), suffix)
+            }
+
+            // Phone
+            "phone_number" => PhoneNumber(locale).fake_with_rng(rng),
+            "cell_number" => CellNumber(locale).fake_with_rng(rng),
+
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn generate_u8<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
         match args.len() {
-            1 => Ok(Value::Number(serde_json::Number::from(Faker.fake::<u8>()))),
+            1 => Ok(Value::Number(serde_json::Number::from(
+                Faker.fake_with_rng::<u8, _>(rng),
+            ))),
             3 => {
                 let min = args[1].as_u64().unwrap_or(0) as u8;
                 let max = args[2].as_u64().unwrap_or(255) as u8;
                 Ok(Value::Number(serde_json::Number::from(
-                    rand::rng().random_range(min..=max),
+                    rng.random_range(min..=max),
                 )))
             }
             _ => Err(DataFakeError::FakeOperatorError(
@@ -321,14 +444,16 @@ impl FakeOperator {
         }
     }
 
-    fn generate_u16(args: &[Value]) -> Result<Value> {
+    fn generate_u16<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
         match args.len() {
-            1 => Ok(Value::Number(serde_json::Number::from(Faker.fake::<u16>()))),
+            1 => Ok(Value::Number(serde_json::Number::from(
+                Faker.fake_with_rng::<u16, _>(rng),
+            ))),
             3 => {
                 let min = args[1].as_u64().unwrap_or(0) as u16;
                 let max = args[2].as_u64().unwrap_or(65535) as u16;
                 Ok(Value::Number(serde_json::Number::from(
-                    rand::rng().random_range(min..=max),
+                    rng.random_range(min..=max),
                 )))
             }
             _ => Err(DataFakeError::FakeOperatorError(
@@ -337,14 +462,16 @@ impl FakeOperator {
         }
     }
 
-    fn generate_u32(args: &[Value]) -> Result<Value> {
+    fn generate_u32<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
         match args.len() {
-            1 => Ok(Value::Number(serde_json::Number::from(Faker.fake::<u32>()))),
+            1 => Ok(Value::Number(serde_json::Number::from(
+                Faker.fake_with_rng::<u32, _>(rng),
+            ))),
             3 => {
                 let min = args[1].as_u64().unwrap_or(0) as u32;
                 let max = args[2].as_u64().unwrap_or(u32::MAX as u64) as u32;
                 Ok(Value::Number(serde_json::Number::from(
-                    rand::rng().random_range(min..=max),
+                    rng.random_range(min..=max),
                 )))
             }
             _ => Err(DataFakeError::FakeOperatorError(
@@ -353,14 +480,16 @@ impl FakeOperator {
         }
     }
 
-    fn generate_u64(args: &[Value]) -> Result<Value> {
+    fn generate_u64<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
         match args.len() {
-            1 => Ok(Value::Number(serde_json::Number::from(Faker.fake::<u64>()))),
+            1 => Ok(Value::Number(serde_json::Number::from(
+                Faker.fake_with_rng::<u64, _>(rng),
+            ))),
             3 => {
                 let min = args[1].as_u64().unwrap_or(0);
                 let max = args[2].as_u64().unwrap_or(u64::MAX);
                 Ok(Value::Number(serde_json::Number::from(
-                    rand::rng().random_range(min..=max),
+                    rng.random_range(min..=max),
                 )))
             }
             _ => Err(DataFakeError::FakeOperatorError(
@@ -369,14 +498,16 @@ impl FakeOperator {
         }
     }
 
-    fn generate_i8(args: &[Value]) -> Result<Value> {
+    fn generate_i8<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
         match args.len() {
-            1 => Ok(Value::Number(serde_json::Number::from(Faker.fake::<i8>()))),
+            1 => Ok(Value::Number(serde_json::Number::from(
+                Faker.fake_with_rng::<i8, _>(rng),
+            ))),
             3 => {
                 let min = args[1].as_i64().unwrap_or(i8::MIN as i64) as i8;
                 let max = args[2].as_i64().unwrap_or(i8::MAX as i64) as i8;
                 Ok(Value::Number(serde_json::Number::from(
-                    rand::rng().random_range(min..=max),
+                    rng.random_range(min..=max),
                 )))
             }
             _ => Err(DataFakeError::FakeOperatorError(
@@ -385,14 +516,16 @@ impl FakeOperator {
         }
     }
 
-    fn generate_i16(args: &[Value]) -> Result<Value> {
+    fn generate_i16<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
         match args.len() {
-            1 => Ok(Value::Number(serde_json::Number::from(Faker.fake::<i16>()))),
+            1 => Ok(Value::Number(serde_json::Number::from(
+                Faker.fake_with_rng::<i16, _>(rng),
+            ))),
             3 => {
                 let min = args[1].as_i64().unwrap_or(i16::MIN as i64) as i16;
                 let max = args[2].as_i64().unwrap_or(i16::MAX as i64) as i16;
                 Ok(Value::Number(serde_json::Number::from(
-                    rand::rng().random_range(min..=max),
+                    rng.random_range(min..=max),
                 )))
             }
             _ => Err(DataFakeError::FakeOperatorError(
@@ -401,14 +534,16 @@ impl FakeOperator {
         }
     }
 
-    fn generate_i32(args: &[Value]) -> Result<Value> {
+    fn generate_i32<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
         match args.len() {
-            1 => Ok(Value::Number(serde_json::Number::from(Faker.fake::<i32>()))),
+            1 => Ok(Value::Number(serde_json::Number::from(
+                Faker.fake_with_rng::<i32, _>(rng),
+            ))),
             3 => {
                 let min = args[1].as_i64().unwrap_or(i32::MIN as i64) as i32;
                 let max = args[2].as_i64().unwrap_or(i32::MAX as i64) as i32;
                 Ok(Value::Number(serde_json::Number::from(
-                    rand::rng().random_range(min..=max),
+                    rng.random_range(min..=max),
                 )))
             }
             _ => Err(DataFakeError::FakeOperatorError(
@@ -417,14 +552,16 @@ impl FakeOperator {
         }
     }
 
-    fn generate_i64(args: &[Value]) -> Result<Value> {
+    fn generate_i64<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
         match args.len() {
-            1 => Ok(Value::Number(serde_json::Number::from(Faker.fake::<i64>()))),
+            1 => Ok(Value::Number(serde_json::Number::from(
+                Faker.fake_with_rng::<i64, _>(rng),
+            ))),
             3 => {
                 let min = args[1].as_i64().unwrap_or(i64::MIN);
                 let max = args[2].as_i64().unwrap_or(i64::MAX);
                 Ok(Value::Number(serde_json::Number::from(
-                    rand::rng().random_range(min..=max),
+                    rng.random_range(min..=max),
                 )))
             }
             _ => Err(DataFakeError::FakeOperatorError(
@@ -433,15 +570,15 @@ impl FakeOperator {
         }
     }
 
-    fn generate_f32(args: &[Value]) -> Result<Value> {
+    fn generate_f32<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
         match args.len() {
             1 => Ok(Value::Number(
-                serde_json::Number::from_f64(Faker.fake::<f32>() as f64).unwrap(),
+                serde_json::Number::from_f64(Faker.fake_with_rng::<f32, _>(rng) as f64).unwrap(),
             )),
             3 => {
                 let min = args[1].as_f64().unwrap_or(0.0) as f32;
                 let max = args[2].as_f64().unwrap_or(1.0) as f32;
-                let value = rand::rng().random_range(min..=max);
+                let value = rng.random_range(min..=max);
                 Ok(Value::Number(
                     serde_json::Number::from_f64(value as f64).unwrap(),
                 ))
@@ -452,15 +589,15 @@ impl FakeOperator {
         }
     }
 
-    fn generate_f64(args: &[Value]) -> Result<Value> {
+    fn generate_f64<R: Rng + ?Sized>(args: &[Value], rng: &mut R) -> Result<Value> {
         match args.len() {
             1 => Ok(Value::Number(
-                serde_json::Number::from_f64(Faker.fake::<f64>()).unwrap(),
+                serde_json::Number::from_f64(Faker.fake_with_rng::<f64, _>(rng)).unwrap(),
             )),
             3 => {
                 let min = args[1].as_f64().unwrap_or(0.0);
                 let max = args[2].as_f64().unwrap_or(1.0);
-                let value = rand::rng().random_range(min..=max);
+                let value = rng.random_range(min..=max);
                 Ok(Value::Number(serde_json::Number::from_f64(value).unwrap()))
             }
             _ => Err(DataFakeError::FakeOperatorError(
@@ -499,6 +636,28 @@ mod tests {
         assert!((10..=20).contains(&value));
     }
 
+    #[test]
+    fn test_generate_numeric_with_distribution() {
+        let args = vec![
+            json!("i32"),
+            json!(0),
+            json!(10),
+            json!({"distribution": "normal", "mean": 5, "std_dev": 1}),
+        ];
+        let value = FakeOperator::generate(&args).unwrap().as_i64().unwrap();
+        assert!((0..=10).contains(&value));
+
+        let args = vec![
+            json!("f64"),
+            json!({"distribution": "exponential", "lambda": 1, "decimals": 1}),
+        ];
+        let value = FakeOperator::generate(&args).unwrap().as_f64().unwrap();
+        assert_eq!((value * 10.0).round() / 10.0, value);
+
+        let args = vec![json!("u8"), json!({"distribution": "poisson"})];
+        assert!(FakeOperator::generate(&args).is_err());
+    }
+
     #[test]
     fn test_generate_name_with_locale() {
         let args = vec![json!("name"), json!("en_US")];
@@ -507,6 +666,40 @@ mod tests {
         assert!(!result.as_str().unwrap().is_empty());
     }
 
+    #[test]
+    fn test_generate_localized_methods() {
+        let methods = [
+            "name",
+            "first_name",
+            "street_address",
+            "city",
+            "phone_number",
+            "company_name",
+        ];
+        for locale in Locale::ALL {
+            for method in methods {
+                let args = vec![json!(method), json!(locale.code())];
+                let result = FakeOperator::generate(&args).unwrap();
+                assert!(!result.as_str().unwrap().is_empty(), "{method} {locale}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_locale_changes_output() {
+        // Japanese names are written in kanji, never ASCII
+        let args = vec![json!("last_name"), json!("ja_JP")];
+        let name = FakeOperator::generate(&args).unwrap();
+        assert!(!name.as_str().unwrap().is_ascii());
+    }
+
+    #[test]
+    fn test_generate_invalid_locale() {
+        let args = vec![json!("name"), json!("xx_XX")];
+        let result = FakeOperator::generate(&args);
+        assert!(matches!(result, Err(DataFakeError::InvalidLocale(_))));
+    }
+
     #[test]
     fn test_generate_email() {
         let args = vec![json!("email")];
@@ -525,6 +718,62 @@ mod tests {
         assert!(password.len() >= 10 && password.len() <= 15);
     }
 
+    #[test]
+    fn test_generate_financial_identifiers() {
+        let iban = FakeOperator::generate(&[json!("iban"), json!("FR")]).unwrap();
+        assert!(finance::validate_iban(iban.as_str().unwrap()));
+        assert_eq!(iban.as_str().unwrap().len(), 27);
+
+        let lei = FakeOperator::generate(&[json!("lei")]).unwrap();
+        assert!(finance::validate_lei(lei.as_str().unwrap()));
+
+        assert!(FakeOperator::generate(&[json!("iban"), json!("XX")]).is_err());
+    }
+
+    #[test]
+    fn test_generate_weighted() {
+        let args = vec![
+            json!("weighted"),
+            json!(["active", 90]),
+            json!(["suspended", 9]),
+            json!(["deleted", 1]),
+            json!(["never", 0]),
+        ];
+        let mut counts = std::collections::HashMap::new();
+        for _ in 0..2000 {
+            let value = FakeOperator::generate(&args).unwrap();
+            *counts
+                .entry(value.as_str().unwrap().to_string())
+                .or_insert(0) += 1;
+        }
+        assert!(counts["active"] > 1600);
+        assert!(counts["suspended"] > 100);
+        assert!(!counts.contains_key("never"));
+
+        assert!(FakeOperator::generate(&[json!("weighted")]).is_err());
+        assert!(FakeOperator::generate(&[json!("weighted"), json!(["a", -1])]).is_err());
+        assert!(FakeOperator::generate(&[json!("weighted"), json!(["a", 0])]).is_err());
+        assert!(FakeOperator::generate(&[json!("weighted"), json!("a")]).is_err());
+        assert!(
+            FakeOperator::generate(&[json!("weighted"), json!(["a", 1e308]), json!(["b", 1e308])])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_generate_regex() {
+        let args = vec![json!("regex"), json!(r"^[A-Z]{2}\d{6}$")];
+        let result = FakeOperator::generate(&args).unwrap();
+        let value = result.as_str().unwrap();
+        assert_eq!(value.len(), 8);
+        assert!(value[..2].chars().all(|c| c.is_ascii_uppercase()));
+        assert!(value[2..].chars().all(|c| c.is_ascii_digit()));
+
+        let args = vec![json!("regex"), json!(r"(a)\1")];
+        let result = FakeOperator::generate(&args);
+        assert!(matches!(result, Err(DataFakeError::InvalidRegex(_))));
+    }
+
     #[test]
     fn test_invalid_method() {
         let args = vec![json!("invalid_method")];
@@ -0,0 +1,258 @@
+use crate::error::{DataFakeError, Result};
+use rand::Rng;
+
+/// BBAN structure per country from the SWIFT IBAN registry, in registry notation:
+/// `n` digits, `a` uppercase letters, `c` uppercase alphanumerics.
+const IBAN_FORMATS: &[(&str, &str)] = &[
+    ("AD", "4n4n12c"),
+    ("AE", "3n16n"),
+    ("AL", "8n16c"),
+    ("AT", "5n11n"),
+    ("AZ", "4a20c"),
+    ("BA", "3n3n8n2n"),
+    ("BE", "3n7n2n"),
+    ("BG", "4a4n2n8c"),
+    ("BH", "4a14c"),
+    ("BR", "8n5n10n1a1c"),
+    ("CH", "5n12c"),
+    ("CR", "4n14n"),
+    ("CY", "3n5n16c"),
+    ("CZ", "4n6n10n"),
+    ("DE", "8n10n"),
+    ("DK", "4n9n1n"),
+    ("DO", "4c20n"),
+    ("EE", "2n2n11n1n"),
+    ("EG", "4n4n17n"),
+    ("ES", "4n4n1n1n10n"),
+    ("FI", "3n11n"),
+    ("FO", "4n9n1n"),
+    ("FR", "5n5n11c2n"),
+    ("GB", "4a6n8n"),
+    ("GE", "2a16n"),
+    ("GI", "4a15c"),
+    ("GL", "4n9n1n"),
+    ("GR", "3n4n16c"),
+    ("GT", "4c20c"),
+    ("HR", "7n10n"),
+    ("HU", "3n4n1n15n1n"),
+    ("IE", "4a6n8n"),
+    ("IL", "3n3n13n"),
+    ("IS", "4n2n6n10n"),
+    ("IT", "1a5n5n12c"),
+    ("JO", "4a4n18c"),
+    ("KW", "4a22c"),
+    ("KZ", "3n13c"),
+    ("LB", "4n20c"),
+    ("LI", "5n12c"),
+    ("LT", "5n11n"),
+    ("LU", "3n13c"),
+    ("LV", "4a13c"),
+    ("MC", "5n5n11c2n"),
+    ("MD", "2c18c"),
+    ("ME", "3n13n2n"),
+    ("MK", "3n10c2n"),
+    ("MR", "5n5n11n2n"),
+    ("MT", "4a5n18c"),
+    ("MU", "4a2n2n12n3n3a"),
+    ("NL", "4a10n"),
+    ("NO", "4n6n1n"),
+    ("PK", "4a16c"),
+    ("PL", "8n16n"),
+    ("PS", "4a21c"),
+    ("PT", "4n4n11n2n"),
+    ("QA", "4a21c"),
+    ("RO", "4a16c"),
+    ("RS", "3n13n2n"),
+    ("SA", "2n18c"),
+    ("SE", "3n16n1n"),
+    ("SI", "5n8n2n"),
+    ("SK", "4n6n10n"),
+    ("SM", "1a5n5n12c"),
+    ("TN", "2n3n13n2n"),
+    ("TR", "5n1n16c"),
+    ("UA", "6n19c"),
+    ("VG", "4a16n"),
+    ("XK", "4n10n2n"),
+];
+
+const DIGITS: &[u8] = b"0123456789";
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Country codes `iban` can generate for.
+pub fn iban_countries() -> impl Iterator<Item = &'static str> {
+    IBAN_FORMATS.iter().map(|(country, _)| *country)
+}
+
+fn bban_format(country: &str) -> Result<&'static str> {
+    IBAN_FORMATS
+        .iter()
+        .find(|(code, _)| *code == country)
+        .map(|(_, format)| *format)
+        .ok_or_else(|| {
+            DataFakeError::FakeOperatorError(format!("Unsupported IBAN country: {country}"))
+        })
+}
+
+/// Splits registry notation such as `4a6n8n` into `(count, kind)` segments.
+fn bban_segments(format: &str) -> impl Iterator<Item = (usize, u8)> + '_ {
+    format
+        .split_inclusive(|c: char| c.is_ascii_alphabetic())
+        .map(|segment| {
+            let (count, kind) = segment.split_at(segment.len() - 1);
+            (count.parse().unwrap_or(0), kind.as_bytes()[0])
+        })
+}
+
+fn charset(kind: u8) -> &'static [u8] {
+    match kind {
+        b'n' => DIGITS,
+        b'a' => LETTERS,
+        _ => ALPHANUMERIC,
+    }
+}
+
+/// Remainder of the ISO 7064 mod 97-10 check over `value`, with letters expanded
+/// to two digits (`A` = 10 ... `Z` = 35).
+fn mod97(value: &str) -> Option<u32> {
+    value.chars().try_fold(0u32, |remainder, c| {
+        let digit = c.to_digit(36)?;
+        Some(if digit < 10 {
+            (remainder * 10 + digit) % 97
+        } else {
+            (remainder * 100 + digit) % 97
+        })
+    })
+}
+
+/// Generates an IBAN for `country` (ISO 3166 alpha-2) with the country's BBAN
+/// format and valid mod-97 check digits.
+pub fn generate_iban<R: Rng + ?Sized>(country: &str, rng: &mut R) -> Result<String> {
+    let country = country.to_ascii_uppercase();
+    let format = bban_format(&country)?;
+    let bban: String = bban_segments(format)
+        .flat_map(|(count, kind)| {
+            let chars = charset(kind);
+            (0..count)
+                .map(|_| chars[rng.random_range(0..chars.len())] as char)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let remainder = mod97(&format!("{bban}{country}00")).unwrap_or(0);
+    Ok(format!("{country}{:02}{bban}", 98 - remainder))
+}
+
+/// Checks an IBAN's length, BBAN format and mod-97 check digits. Spaces are
+/// ignored, so both electronic and print formats are accepted.
+pub fn validate_iban(iban: &str) -> bool {
+    let iban: String = iban.chars().filter(|c| *c != ' ').collect();
+    if iban.len() < 5 || !iban.is_ascii() {
+        return false;
+    }
+    let (country, rest) = iban.split_at(2);
+    let (check, bban) = rest.split_at(2);
+    let Ok(format) = bban_format(country) else {
+        return false;
+    };
+    if !check.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+
+    let mut remaining = bban.as_bytes();
+    for (count, kind) in bban_segments(format) {
+        if remaining.len() < count {
+            return false;
+        }
+        let (segment, rest) = remaining.split_at(count);
+        if !segment.iter().all(|b| charset(kind).contains(b)) {
+            return false;
+        }
+        remaining = rest;
+    }
+    remaining.is_empty() && mod97(&format!("{bban}{country}{check}")) == Some(1)
+}
+
+/// Generates an ISO 17442 Legal Entity Identifier: a 4-character LOU prefix,
+/// `00`, a 12-character entity part and two mod-97 check digits.
+pub fn generate_lei<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let prefix: String = (0..4)
+        .map(|_| DIGITS[rng.random_range(0..DIGITS.len())] as char)
+        .collect();
+    let entity: String = (0..12)
+        .map(|_| ALPHANUMERIC[rng.random_range(0..ALPHANUMERIC.len())] as char)
+        .collect();
+    let base = format!("{prefix}00{entity}");
+    let remainder = mod97(&format!("{base}00")).unwrap_or(0);
+    format!("{base}{:02}", 98 - remainder)
+}
+
+/// Checks that an LEI is 20 uppercase alphanumerics ending in valid mod-97 check
+/// digits.
+pub fn validate_lei(lei: &str) -> bool {
+    lei.len() == 20
+        && lei.bytes().all(|b| ALPHANUMERIC.contains(&b))
+        && lei[18..].bytes().all(|b| b.is_ascii_digit())
+        && mod97(lei) == Some(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_iban_lengths_and_checksums() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for (country, length) in [("DE", 22), ("GB", 22), ("FR", 27), ("NO", 15), ("MT", 31)] {
+            let iban = generate_iban(country, &mut rng).unwrap();
+            assert_eq!(iban.len(), length, "{iban}");
+            assert!(iban.starts_with(country));
+        }
+
+        for country in iban_countries() {
+            for _ in 0..20 {
+                let iban = generate_iban(country, &mut rng).unwrap();
+                assert!(validate_iban(&iban), "{iban}");
+            }
+        }
+
+        let gb = generate_iban("gb", &mut rng).unwrap();
+        assert!(gb[4..8].bytes().all(|b| b.is_ascii_uppercase()));
+        assert!(gb[8..].bytes().all(|b| b.is_ascii_digit()));
+
+        assert!(generate_iban("XX", &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_validate_iban() {
+        // Published example IBANs
+        assert!(validate_iban("DE89370400440532013000"));
+        assert!(validate_iban("GB29 NWBK 6016 1331 9268 19"));
+        assert!(validate_iban("FR1420041010050500013M02606"));
+
+        assert!(!validate_iban("DE89370400440532013001")); // check digits
+        assert!(!validate_iban("DE8937040044053201300")); // length
+        assert!(!validate_iban("GB29NWBK60161331926819X"));
+        assert!(!validate_iban("XX89370400440532013000"));
+        assert!(!validate_iban(""));
+    }
+
+    #[test]
+    fn test_lei() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..100 {
+            let lei = generate_lei(&mut rng);
+            assert_eq!(lei.len(), 20);
+            assert_eq!(&lei[4..6], "00");
+            assert!(validate_lei(&lei), "{lei}");
+        }
+
+        // Published LEIs
+        assert!(validate_lei("5493001KJTIIGC8Y1R12"));
+        assert!(validate_lei("7LTWFZYICNSX8D621K86"));
+        assert!(!validate_lei("5493001KJTIIGC8Y1R13"));
+        assert!(!validate_lei("5493001KJTIIGC8Y1R1"));
+    }
+}
@@ -1,3 +1,12 @@
+pub mod datetime;
+pub mod distribution;
 pub mod fake;
+pub mod finance;
+pub mod regex;
+pub mod registry;
 
+pub use distribution::{Distribution, NumericOptions};
 pub use fake::FakeOperator;
+pub use finance::{validate_iban, validate_lei};
+pub use regex::RegexGenerator;
+pub use registry::{ArgType, MethodSpec, Param};
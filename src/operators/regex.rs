@@ -0,0 +1,589 @@
+use crate::error::{DataFakeError, Result};
+use rand::Rng;
+
+/// Extra repetitions generated for unbounded quantifiers (`*`, `+`, `{n,}`).
+pub const UNBOUNDED_REPEAT_LIMIT: u32 = 10;
+
+/// Largest repetition count accepted in `{n}` / `{n,m}`.
+const MAX_REPEAT: u32 = 1000;
+
+/// Longest string, in characters, a pattern may generate. Nested quantifiers
+/// multiply, so this is checked for the whole pattern rather than per quantifier.
+pub const MAX_LENGTH: u64 = 64 * 1024;
+
+/// Printable ASCII, used for `.` and negated classes.
+const PRINTABLE: (char, char) = (' ', '~');
+
+const DIGIT: &[(char, char)] = &[('0', '9')];
+const WORD: &[(char, char)] = &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')];
+const SPACE: &[(char, char)] = &[('\t', '\t'), (' ', ' ')];
+
+/// Generates strings matching a regular expression.
+///
+/// Supports literals, escapes, `.`, character classes (ranges, negation, `\d\w\s`
+/// and POSIX classes), groups, alternation, anchors and quantifiers. Backreferences,
+/// lookaround and inline flags are rejected. Unbounded quantifiers repeat at most
+/// [`UNBOUNDED_REPEAT_LIMIT`] times beyond their minimum, and patterns that could
+/// generate more than [`MAX_LENGTH`] characters are rejected.
+#[derive(Debug, Clone)]
+pub struct RegexGenerator {
+    root: Node,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Empty,
+    Literal(char),
+    Class(Vec<(char, char)>),
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat { node: Box<Node>, min: u32, max: u32 },
+}
+
+impl RegexGenerator {
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let mut parser = Parser {
+            pattern,
+            chars: pattern.chars().collect(),
+            pos: 0,
+        };
+        let root = parser.parse_alternation()?;
+        if parser.pos < parser.chars.len() {
+            return Err(parser.error("unmatched ')'"));
+        }
+        let max_len = root.max_len();
+        if max_len > MAX_LENGTH {
+            return Err(DataFakeError::InvalidRegex(format!(
+                "'{pattern}' can generate {max_len} characters, more than the limit of {MAX_LENGTH}"
+            )));
+        }
+        Ok(Self { root })
+    }
+
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let mut output = String::new();
+        self.root.generate(rng, &mut output);
+        output
+    }
+}
+
+impl Node {
+    /// Length of the longest string the node can generate.
+    fn max_len(&self) -> u64 {
+        match self {
+            Node::Empty => 0,
+            Node::Literal(_) | Node::Class(_) => 1,
+            Node::Concat(nodes) => nodes.iter().map(Node::max_len).fold(0, u64::saturating_add),
+            Node::Alternation(branches) => branches.iter().map(Node::max_len).max().unwrap_or(0),
+            Node::Repeat { node, max, .. } => node.max_len().saturating_mul(*max as u64),
+        }
+    }
+
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R, output: &mut String) {
+        match self {
+            Node::Empty => {}
+            Node::Literal(c) => output.push(*c),
+            Node::Class(ranges) => output.push(pick_from_ranges(ranges, rng)),
+            Node::Concat(nodes) => nodes.iter().for_each(|node| node.generate(rng, output)),
+            Node::Alternation(branches) => {
+                branches[rng.random_range(0..branches.len())].generate(rng, output)
+            }
+            Node::Repeat { node, min, max } => {
+                for _ in 0..rng.random_range(*min..=*max) {
+                    node.generate(rng, output);
+                }
+            }
+        }
+    }
+}
+
+fn pick_from_ranges<R: Rng + ?Sized>(ranges: &[(char, char)], rng: &mut R) -> char {
+    let total: u32 = ranges
+        .iter()
+        .map(|(lo, hi)| *hi as u32 - *lo as u32 + 1)
+        .sum();
+    let mut n = rng.random_range(0..total);
+    for (lo, hi) in ranges {
+        let size = *hi as u32 - *lo as u32 + 1;
+        if n < size {
+            // Ranges never span the surrogate gap, see `normalize`
+            return char::from_u32(*lo as u32 + n).unwrap_or(*lo);
+        }
+        n -= size;
+    }
+    unreachable!("class ranges are never empty")
+}
+
+/// Sorts and merges ranges, splitting any that span the UTF-16 surrogate gap.
+fn normalize(mut ranges: Vec<(char, char)>) -> Vec<(char, char)> {
+    ranges.sort();
+    let mut merged: Vec<(char, char)> = Vec::with_capacity(ranges.len());
+    for (lo, hi) in ranges {
+        match merged.last_mut() {
+            Some((_, last_hi)) if lo as u32 <= *last_hi as u32 + 1 => *last_hi = (*last_hi).max(hi),
+            _ => merged.push((lo, hi)),
+        }
+    }
+    merged
+        .into_iter()
+        .flat_map(|(lo, hi)| {
+            if (lo as u32) < 0xD800 && hi as u32 > 0xDFFF {
+                vec![(lo, '\u{D7FF}'), ('\u{E000}', hi)]
+            } else {
+                vec![(lo, hi)]
+            }
+        })
+        .collect()
+}
+
+/// The printable ASCII characters not in `ranges`.
+fn negate(ranges: &[(char, char)]) -> Vec<(char, char)> {
+    let ranges = normalize(ranges.to_vec());
+    let mut result = Vec::new();
+    let mut next = PRINTABLE.0 as u32;
+    for (lo, hi) in ranges {
+        let (lo, hi) = (lo as u32, hi as u32);
+        if hi < next {
+            continue;
+        }
+        if lo > next {
+            result.push((next, (lo - 1).min(PRINTABLE.1 as u32)));
+        }
+        next = hi + 1;
+        if next > PRINTABLE.1 as u32 {
+            break;
+        }
+    }
+    if next <= PRINTABLE.1 as u32 {
+        result.push((next, PRINTABLE.1 as u32));
+    }
+    result
+        .into_iter()
+        .filter(|(lo, hi)| lo <= hi)
+        .filter_map(|(lo, hi)| Some((char::from_u32(lo)?, char::from_u32(hi)?)))
+        .collect()
+}
+
+struct Parser<'a> {
+    pattern: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> DataFakeError {
+        DataFakeError::InvalidRegex(format!(
+            "{message} at position {} in '{}'",
+            self.pos, self.pattern
+        ))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += c.is_some() as usize;
+        c
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_alternation(&mut self) -> Result<Node> {
+        let mut branches = vec![self.parse_concat()?];
+        while self.eat('|') {
+            branches.push(self.parse_concat()?);
+        }
+        Ok(if branches.len() == 1 {
+            branches.pop().unwrap_or(Node::Empty)
+        } else {
+            Node::Alternation(branches)
+        })
+    }
+
+    fn parse_concat(&mut self) -> Result<Node> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            nodes.push(self.parse_quantifier(atom)?);
+        }
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.pop().unwrap_or(Node::Empty),
+            _ => Node::Concat(nodes),
+        })
+    }
+
+    fn parse_atom(&mut self) -> Result<Node> {
+        let Some(c) = self.next() else {
+            return Ok(Node::Empty);
+        };
+        match c {
+            '(' => self.parse_group(),
+            '[' => Ok(Node::Class(self.parse_class()?)),
+            '.' => Ok(Node::Class(vec![PRINTABLE])),
+            '^' | '$' => Ok(Node::Empty),
+            '\\' => self.parse_escape(),
+            '*' | '+' | '?' | '{' => {
+                self.pos -= 1;
+                Err(self.error("nothing to repeat"))
+            }
+            c => Ok(Node::Literal(c)),
+        }
+    }
+
+    fn parse_group(&mut self) -> Result<Node> {
+        if self.eat('?') {
+            if self.eat(':') {
+                // Non-capturing group
+            } else if self.eat('P') {
+                if !self.eat('<') {
+                    return Err(self.error("backreferences are not supported"));
+                }
+                self.skip_group_name();
+            } else if self.eat('<') {
+                if matches!(self.peek(), Some('=' | '!')) {
+                    return Err(self.error("lookbehind is not supported"));
+                }
+                self.skip_group_name();
+            } else if matches!(self.peek(), Some('=' | '!')) {
+                return Err(self.error("lookahead is not supported"));
+            } else {
+                return Err(self.error("inline flags are not supported"));
+            }
+        }
+        let node = self.parse_alternation()?;
+        if !self.eat(')') {
+            return Err(self.error("unclosed group"));
+        }
+        Ok(node)
+    }
+
+    /// Skips the `name>` of a named group; names don't affect what is generated.
+    fn skip_group_name(&mut self) {
+        while self.next().is_some_and(|c| c != '>') {}
+    }
+
+    fn parse_escape(&mut self) -> Result<Node> {
+        let Some(c) = self.next() else {
+            return Err(self.error("trailing backslash"));
+        };
+        Ok(match c {
+            'b' | 'B' | 'A' | 'z' | 'Z' => Node::Empty,
+            '1'..='9' | 'k' => {
+                self.pos -= 2;
+                return Err(self.error("backreferences are not supported"));
+            }
+            _ => match self.class_escape(c)? {
+                Ok(ranges) => Node::Class(ranges),
+                Err(c) => Node::Literal(c),
+            },
+        })
+    }
+
+    /// Escapes valid both inside and outside classes: either a set of ranges or a
+    /// single character.
+    fn class_escape(&mut self, c: char) -> Result<std::result::Result<Vec<(char, char)>, char>> {
+        Ok(match c {
+            'd' => Ok(DIGIT.to_vec()),
+            'D' => Ok(negate(DIGIT)),
+            'w' => Ok(WORD.to_vec()),
+            'W' => Ok(negate(WORD)),
+            's' => Ok(SPACE.to_vec()),
+            'S' => Ok(negate(SPACE)),
+            'n' => Err('\n'),
+            't' => Err('\t'),
+            'r' => Err('\r'),
+            'f' => Err('\x0C'),
+            'v' => Err('\x0B'),
+            '0' => Err('\0'),
+            'x' => Err(self.parse_hex_escape()?),
+            'u' => Err(self.parse_hex_escape()?),
+            'p' | 'P' => {
+                self.pos -= 2;
+                return Err(self.error("Unicode classes are not supported"));
+            }
+            c if c.is_ascii_alphanumeric() => {
+                self.pos -= 2;
+                return Err(self.error(&format!("unknown escape '\\{c}'")));
+            }
+            c => Err(c),
+        })
+    }
+
+    /// Parses `\xHH`, `\uHHHH` or the braced forms `\x{H..}` / `\u{H..}`.
+    fn parse_hex_escape(&mut self) -> Result<char> {
+        let digits: String = if self.eat('{') {
+            let start = self.pos;
+            while self.peek().is_some_and(|c| c != '}') {
+                self.pos += 1;
+            }
+            let digits = self.chars[start..self.pos].iter().collect();
+            if !self.eat('}') {
+                return Err(self.error("unclosed hex escape"));
+            }
+            digits
+        } else {
+            let len = if self.chars[self.pos - 1] == 'x' {
+                2
+            } else {
+                4
+            };
+            let end = (self.pos + len).min(self.chars.len());
+            let digits = self.chars[self.pos..end].iter().collect();
+            self.pos = end;
+            digits
+        };
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error(&format!("invalid hex escape '{digits}'")))
+    }
+
+    fn parse_class(&mut self) -> Result<Vec<(char, char)>> {
+        let negated = self.eat('^');
+        let mut ranges = Vec::new();
+        let mut first = true;
+        loop {
+            let Some(c) = self.next() else {
+                return Err(self.error("unclosed character class"));
+            };
+            let start = match c {
+                ']' if !first => break,
+                '[' if self.peek() == Some(':') => {
+                    ranges.extend(self.parse_posix_class()?);
+                    first = false;
+                    continue;
+                }
+                '\\' => {
+                    let Some(escaped) = self.next() else {
+                        return Err(self.error("trailing backslash"));
+                    };
+                    match self.class_escape(escaped)? {
+                        Ok(set) => {
+                            ranges.extend(set);
+                            first = false;
+                            continue;
+                        }
+                        Err(c) => c,
+                    }
+                }
+                c => c,
+            };
+            first = false;
+
+            // A '-' before the closing bracket is literal
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|&c| c != ']') {
+                self.pos += 1;
+                let end = match self.next() {
+                    Some('\\') => match self.next().map(|c| self.class_escape(c)).transpose()? {
+                        Some(Err(c)) => c,
+                        _ => return Err(self.error("invalid range end in character class")),
+                    },
+                    Some(c) => c,
+                    None => return Err(self.error("unclosed character class")),
+                };
+                if end < start {
+                    return Err(self.error(&format!("invalid range '{start}-{end}'")));
+                }
+                ranges.push((start, end));
+            } else {
+                ranges.push((start, start));
+            }
+        }
+
+        let ranges = if negated {
+            negate(&ranges)
+        } else {
+            normalize(ranges)
+        };
+        if ranges.is_empty() {
+            return Err(self.error("character class matches nothing"));
+        }
+        Ok(ranges)
+    }
+
+    /// Parses `[:name:]` after the opening `[`.
+    fn parse_posix_class(&mut self) -> Result<Vec<(char, char)>> {
+        let start = self.pos + 1;
+        let Some(len) = self.chars[start..].windows(2).position(|w| w == [':', ']']) else {
+            return Err(self.error("unclosed POSIX class"));
+        };
+        let name: String = self.chars[start..start + len].iter().collect();
+        self.pos = start + len + 2;
+        Ok(match name.as_str() {
+            "alpha" => vec![('A', 'Z'), ('a', 'z')],
+            "digit" => DIGIT.to_vec(),
+            "alnum" => vec![('0', '9'), ('A', 'Z'), ('a', 'z')],
+            "upper" => vec![('A', 'Z')],
+            "lower" => vec![('a', 'z')],
+            "space" => SPACE.to_vec(),
+            "xdigit" => vec![('0', '9'), ('A', 'F'), ('a', 'f')],
+            "punct" => vec![('!', '/'), (':', '@'), ('[', '`'), ('{', '~')],
+            "word" => WORD.to_vec(),
+            _ => return Err(self.error(&format!("unknown POSIX class '{name}'"))),
+        })
+    }
+
+    fn parse_quantifier(&mut self, node: Node) -> Result<Node> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, UNBOUNDED_REPEAT_LIMIT),
+            Some('+') => (1, 1 + UNBOUNDED_REPEAT_LIMIT),
+            Some('?') => (0, 1),
+            Some('{') => self.parse_bounds()?,
+            _ => return Ok(node),
+        };
+        self.pos += 1;
+        // Lazy and possessive modifiers don't change what matches
+        if !self.eat('?') {
+            self.eat('+');
+        }
+        if matches!(self.peek(), Some('*' | '+' | '?' | '{')) {
+            return Err(self.error("nothing to repeat"));
+        }
+        Ok(Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+        })
+    }
+
+    /// Parses `{n}`, `{n,}` or `{n,m}`, leaving the closing brace for the caller.
+    fn parse_bounds(&mut self) -> Result<(u32, u32)> {
+        let start = self.pos;
+        let Some(len) = self.chars[start..].iter().position(|&c| c == '}') else {
+            return Err(self.error("unclosed repetition"));
+        };
+        let body: String = self.chars[start + 1..start + len].iter().collect();
+        let parse = |s: &str| -> Result<u32> {
+            let n = s
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| self.error(&format!("invalid repetition '{{{body}}}'")))?;
+            if n > MAX_REPEAT {
+                return Err(self.error(&format!("repetition count exceeds {MAX_REPEAT}")));
+            }
+            Ok(n)
+        };
+        let bounds = match body.split_once(',') {
+            None => {
+                let n = parse(&body)?;
+                (n, n)
+            }
+            Some((min, "")) => {
+                let min = parse(min)?;
+                (min, min + UNBOUNDED_REPEAT_LIMIT)
+            }
+            Some((min, max)) => (parse(min)?, parse(max)?),
+        };
+        if bounds.0 > bounds.1 {
+            return Err(self.error(&format!("invalid repetition '{{{body}}}'")));
+        }
+        self.pos = start + len;
+        Ok(bounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn samples(pattern: &str) -> Vec<String> {
+        let generator = RegexGenerator::parse(pattern).unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        (0..200).map(|_| generator.generate(&mut rng)).collect()
+    }
+
+    #[test]
+    fn test_classes_and_quantifiers() {
+        for s in samples(r"^[A-Z]{3}-\d{4}$") {
+            assert_eq!(s.len(), 8);
+            assert!(s[..3].chars().all(|c| c.is_ascii_uppercase()));
+            assert_eq!(&s[3..4], "-");
+            assert!(s[4..].chars().all(|c| c.is_ascii_digit()));
+        }
+
+        for s in samples(r"[^a-z0-9]{2,5}") {
+            assert!((2..=5).contains(&s.len()));
+            assert!(
+                s.chars()
+                    .all(|c| !c.is_ascii_lowercase() && !c.is_ascii_digit())
+            );
+        }
+
+        for s in samples(r"\w+@[[:alpha:]]{1,3}\.(com|org)") {
+            let (user, domain) = s.split_once('@').unwrap();
+            assert!((1..=1 + UNBOUNDED_REPEAT_LIMIT as usize).contains(&user.len()));
+            assert!(domain.ends_with(".com") || domain.ends_with(".org"));
+        }
+    }
+
+    #[test]
+    fn test_groups_and_alternation() {
+        let values = samples(r"(?:ACCT|CUST)_(?P<n>[0-9a-f]{2})?x*");
+        for s in &values {
+            assert!(s.starts_with("ACCT_") || s.starts_with("CUST_"));
+            assert!(s.len() <= 5 + 2 + UNBOUNDED_REPEAT_LIMIT as usize);
+        }
+        assert!(values.iter().any(|s| s.starts_with("ACCT")));
+        assert!(values.iter().any(|s| s.starts_with("CUST")));
+
+        for s in samples(r"\(\x41é\.\)") {
+            assert_eq!(s, "(Aé.)");
+        }
+        assert!(samples("").iter().all(String::is_empty));
+    }
+
+    #[test]
+    fn test_length_limit() {
+        // Nested quantifiers multiply: 100 * 100 * 6 characters
+        let generator = RegexGenerator::parse(r"((abc|defghi){100}){100}").unwrap();
+        assert!(generator.generate(&mut StdRng::seed_from_u64(1)).len() <= 60_000);
+
+        let err = RegexGenerator::parse(r"((a{1000}){1000}){1000}").unwrap_err();
+        assert!(err.to_string().contains("1000000000 characters"), "{err}");
+    }
+
+    #[test]
+    fn test_unsupported_constructs() {
+        for pattern in [
+            r"(a)\1",
+            r"(?<name>a)\k<name>",
+            r"(?=a)",
+            r"(?<!a)b",
+            r"(?P<n>a)(?P=n)",
+            r"(?i)abc",
+            r"\p{L}",
+            r"*a",
+            r"a{3,1}",
+            r"a{5000}",
+            r"((a{1000}){1000}){1000}",
+            r"(a{100}|b){1000}",
+            r"[z-a]",
+            r"(abc",
+            r"abc)",
+            r"[abc",
+        ] {
+            assert!(
+                matches!(
+                    RegexGenerator::parse(pattern),
+                    Err(DataFakeError::InvalidRegex(_))
+                ),
+                "{pattern} should be rejected"
+            );
+        }
+    }
+}
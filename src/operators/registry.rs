@@ -0,0 +1,394 @@
+//! Signatures of the `fake` methods, used to check calls when a config is
+//! parsed rather than when it generates.
+
+use crate::error::{DataFakeError, Result};
+use serde_json::Value;
+
+/// What a fake method argument accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgType {
+    /// A non-negative integer, such as a length or count
+    Count,
+    /// An integer within `min..=max`, such as a bound of `u8`
+    Integer {
+        min: i64,
+        max: u64,
+    },
+    Number,
+    /// A number within the `f32` range
+    Float32,
+    String,
+    /// A locale code such as `fr_FR`, checked against the supported locales by
+    /// the config parser
+    Locale,
+    /// A date or datetime string, a relative offset, a unix timestamp or `null`
+    Instant,
+    /// A string, or `null` for the default
+    OptionalString,
+    /// Any JSON value
+    Any,
+    /// A `[value, weight]` pair with a number weight
+    WeightedPair,
+}
+
+impl ArgType {
+    fn accepts(self, value: &Value) -> bool {
+        match self {
+            ArgType::Count => value.is_u64(),
+            ArgType::Integer { min, max } => match value.as_i64() {
+                Some(n) if n < 0 => n >= min,
+                _ => value.as_u64().is_some_and(|n| n <= max),
+            },
+            ArgType::Number => value.is_number(),
+            ArgType::Float32 => value.as_f64().is_some_and(|n| n.abs() <= f32::MAX as f64),
+            ArgType::String | ArgType::Locale => value.is_string(),
+            ArgType::Instant => value.is_string() || value.is_i64() || value.is_null(),
+            ArgType::OptionalString => value.is_string() || value.is_null(),
+            ArgType::Any => true,
+            ArgType::WeightedPair => matches!(
+                value,
+                Value::Array(pair) if pair.len() == 2 && pair[1].is_number()
+            ),
+        }
+    }
+
+    fn describe(self) -> String {
+        match self {
+            ArgType::Count => "a non-negative integer".to_string(),
+            ArgType::Integer { min, max } => format!("an integer from {min} to {max}"),
+            ArgType::Number => "a number".to_string(),
+            ArgType::Float32 => "a number within the f32 range".to_string(),
+            ArgType::String => "a string".to_string(),
+            ArgType::Locale => "a locale code".to_string(),
+            ArgType::Instant => "a date string, unix timestamp or null".to_string(),
+            ArgType::OptionalString => "a string or null".to_string(),
+            ArgType::Any => "any value".to_string(),
+            ArgType::WeightedPair => "a [value, weight] pair".to_string(),
+        }
+    }
+}
+
+/// A named, typed argument of a fake method.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    pub kind: ArgType,
+}
+
+/// The arguments a fake method accepts after its name.
+#[derive(Debug, Clone, Copy)]
+pub struct MethodSpec {
+    pub name: &'static str,
+    pub params: &'static [Param],
+    /// How many leading `params` must be given; the rest are optional
+    pub required: usize,
+    /// Type of any arguments after `params`, for methods taking a list of values
+    pub variadic: Option<ArgType>,
+    /// Whether a trailing options object (a numeric distribution) is accepted
+    pub options: bool,
+}
+
+const fn param(name: &'static str, kind: ArgType) -> Param {
+    Param { name, kind }
+}
+
+const fn method(name: &'static str, params: &'static [Param]) -> MethodSpec {
+    MethodSpec {
+        name,
+        params,
+        required: 0,
+        variadic: None,
+        options: false,
+    }
+}
+
+const fn numeric(name: &'static str, bounds: &'static [Param]) -> MethodSpec {
+    MethodSpec {
+        options: true,
+        ..method(name, bounds)
+    }
+}
+
+/// `min, max` parameters of a numeric method.
+const fn bounds(kind: ArgType) -> [Param; 2] {
+    [param("min", kind), param("max", kind)]
+}
+
+const fn integer(min: i64, max: u64) -> [Param; 2] {
+    bounds(ArgType::Integer { min, max })
+}
+
+const fn variadic(name: &'static str, kind: ArgType) -> MethodSpec {
+    MethodSpec {
+        required: 1,
+        variadic: Some(kind),
+        ..method(name, &[])
+    }
+}
+
+const NONE: &[Param] = &[];
+const LOCALIZED: &[Param] = &[param("locale", ArgType::Locale)];
+const LENGTHS: &[Param] = &[
+    param("min_length", ArgType::Count),
+    param("max_length", ArgType::Count),
+];
+const DATETIME: &[Param] = &[
+    param("start", ArgType::Instant),
+    param("end", ArgType::Instant),
+    param("format", ArgType::OptionalString),
+    param("timezone", ArgType::OptionalString),
+];
+
+/// Every fake method, including aliases.
+pub const METHODS: &[MethodSpec] = &[
+    // Numeric
+    numeric("u8", &integer(0, u8::MAX as u64)),
+    numeric("u16", &integer(0, u16::MAX as u64)),
+    numeric("u32", &integer(0, u32::MAX as u64)),
+    numeric("u64", &integer(0, u64::MAX)),
+    numeric("i8", &integer(i8::MIN as i64, i8::MAX as u64)),
+    numeric("i16", &integer(i16::MIN as i64, i16::MAX as u64)),
+    numeric("i32", &integer(i32::MIN as i64, i32::MAX as u64)),
+    numeric("i64", &integer(i64::MIN, i64::MAX as u64)),
+    numeric("f32", &bounds(ArgType::Float32)),
+    numeric("f64", &bounds(ArgType::Number)),
+    method("bool", NONE),
+    method("boolean", NONE),
+    method("uuid", NONE),
+    // Address
+    method("latitude", NONE),
+    method("longitude", NONE),
+    method("street_address", LOCALIZED),
+    method("street_name", LOCALIZED),
+    method("street_suffix", LOCALIZED),
+    method("city", LOCALIZED),
+    method("city_name", LOCALIZED),
+    method("state_name", LOCALIZED),
+    method("state_abbr", LOCALIZED),
+    method("country_name", LOCALIZED),
+    method("country_code", LOCALIZED),
+    method("zip_code", LOCALIZED),
+    method("zip", LOCALIZED),
+    method("post_code", LOCALIZED),
+    method("postcode", LOCALIZED),
+    method("postal_code", LOCALIZED),
+    // Personal
+    method("name", LOCALIZED),
+    method("full_name", LOCALIZED),
+    method("first_name", LOCALIZED),
+    method("last_name", LOCALIZED),
+    method("name_with_title", LOCALIZED),
+    method("title", LOCALIZED),
+    method("suffix", LOCALIZED),
+    method("phone_number", LOCALIZED),
+    method("cell_number", LOCALIZED),
+    // Company
+    method("company_name", LOCALIZED),
+    method("company_suffix", LOCALIZED),
+    method("industry", LOCALIZED),
+    method("profession", LOCALIZED),
+    method("catch_phrase", LOCALIZED),
+    method("bs", LOCALIZED),
+    method("bs_adj", LOCALIZED),
+    method("bs_noun", LOCALIZED),
+    method("bs_verb", LOCALIZED),
+    // Internet
+    method("email", LOCALIZED),
+    method("safe_email", LOCALIZED),
+    method("free_email", LOCALIZED),
+    method("username", LOCALIZED),
+    method("domain_suffix", LOCALIZED),
+    method("domain_name", LOCALIZED),
+    method("password", LENGTHS),
+    method("ipv4", NONE),
+    method("ipv6", NONE),
+    method("mac_address", NONE),
+    method("user_agent", NONE),
+    // Finance
+    method("bic", NONE),
+    method("credit_card_number", NONE),
+    method("currency_code", NONE),
+    method("currency_name", NONE),
+    method("currency_symbol", NONE),
+    method("iban", &[param("country", ArgType::String)]),
+    method("lei", NONE),
+    // Content
+    method("word", NONE),
+    method("words", &[param("count", ArgType::Count)]),
+    method(
+        "sentence",
+        &[
+            param("min_words", ArgType::Count),
+            param("max_words", ArgType::Count),
+        ],
+    ),
+    method(
+        "paragraph",
+        &[
+            param("min_sentences", ArgType::Count),
+            param("max_sentences", ArgType::Count),
+        ],
+    ),
+    method("isbn10", NONE),
+    method("isbn13", NONE),
+    method("file_name", NONE),
+    method("file_extension", NONE),
+    method("dir_path", NONE),
+    method("file_path", NONE),
+    // Dates and times
+    method("datetime", DATETIME),
+    method("iso8601_datetime", DATETIME),
+    method("date", DATETIME),
+    method("time", NONE),
+    method("month_name", NONE),
+    // Patterns and choices
+    method("alphanumeric", LENGTHS),
+    MethodSpec {
+        required: 1,
+        ..method("regex", &[param("pattern", ArgType::String)])
+    },
+    variadic("enum", ArgType::Any),
+    variadic("pick", ArgType::Any),
+    variadic("choice", ArgType::Any),
+    variadic("weighted", ArgType::WeightedPair),
+    variadic("weighted_choice", ArgType::WeightedPair),
+];
+
+/// Looks up the signature of a fake method.
+pub fn lookup(name: &str) -> Option<&'static MethodSpec> {
+    METHODS.iter().find(|spec| spec.name == name)
+}
+
+impl MethodSpec {
+    /// The method's signature, e.g. `password(min_length?, max_length?)`.
+    pub fn signature(&self) -> String {
+        let mut params: Vec<String> = self
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                let optional = if i < self.required { "" } else { "?" };
+                format!("{}{optional}", param.name)
+            })
+            .collect();
+        if self.variadic.is_some() {
+            params.push("values...".to_string());
+        }
+        if self.options {
+            params.push("options?".to_string());
+        }
+        format!("{}({})", self.name, params.join(", "))
+    }
+
+    /// Checks the number and types of `args`, the arguments after the method name.
+    pub fn check(&self, args: &[Value]) -> Result<()> {
+        let args = match args.split_last() {
+            Some((Value::Object(_), rest)) if self.options => rest,
+            _ => args,
+        };
+        let max = if self.variadic.is_some() {
+            usize::MAX
+        } else {
+            self.params.len()
+        };
+        if args.len() < self.required || args.len() > max {
+            return Err(DataFakeError::InvalidConfig(format!(
+                "Fake method '{}' got {} argument{}, expected {}",
+                self.name,
+                args.len(),
+                if args.len() == 1 { "" } else { "s" },
+                self.signature()
+            )));
+        }
+
+        for (i, arg) in args.iter().enumerate() {
+            let (name, kind) = match self.params.get(i) {
+                Some(param) => (param.name, param.kind),
+                None => ("value", self.variadic.unwrap_or(ArgType::Any)),
+            };
+            if !kind.accepts(arg) {
+                return Err(DataFakeError::InvalidConfig(format!(
+                    "Argument {} ({name}) of fake method '{}' must be {}, got {arg}",
+                    i + 1,
+                    self.name,
+                    kind.describe()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operators::FakeOperator;
+    use crate::operators::fake::{LOCALIZED_METHODS, NUMERIC_METHODS};
+    use serde_json::json;
+
+    #[test]
+    fn test_every_method_is_registered_and_generates() {
+        for name in LOCALIZED_METHODS {
+            assert_eq!(lookup(name).unwrap().params[0].kind, ArgType::Locale);
+        }
+        for name in NUMERIC_METHODS {
+            assert!(lookup(name).unwrap().options);
+        }
+        for spec in METHODS {
+            let mut args = vec![json!(spec.name)];
+            args.extend(match spec.name {
+                "regex" => vec![json!("[a-z]{3}")],
+                "enum" | "pick" | "choice" => vec![json!("a")],
+                "weighted" | "weighted_choice" => vec![json!(["a", 1])],
+                _ => vec![],
+            });
+            assert!(
+                FakeOperator::generate(&args).is_ok(),
+                "{} failed to generate",
+                spec.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_arguments() {
+        let password = lookup("password").unwrap();
+        assert_eq!(password.signature(), "password(min_length?, max_length?)");
+        assert!(password.check(&[json!(8), json!(16)]).is_ok());
+        assert!(password.check(&[json!("8")]).is_err());
+        assert!(password.check(&[json!(8), json!(16), json!(24)]).is_err());
+
+        let f64 = lookup("f64").unwrap();
+        assert!(
+            f64.check(&[json!(0), json!(1), json!({"decimals": 2})])
+                .is_ok()
+        );
+        assert!(f64.check(&[json!({"distribution": "normal"})]).is_ok());
+        assert!(f64.check(&[json!("low"), json!(1)]).is_err());
+
+        // Integer bounds must fit the method's type
+        let u8 = lookup("u8").unwrap();
+        assert!(u8.check(&[json!(0), json!(255)]).is_ok());
+        assert!(u8.check(&[json!(200), json!(300)]).is_err());
+        assert!(u8.check(&[json!(-1), json!(10)]).is_err());
+        assert!(u8.check(&[json!(1.5), json!(10)]).is_err());
+        let i64 = lookup("i64").unwrap();
+        assert!(i64.check(&[json!(i64::MIN), json!(i64::MAX)]).is_ok());
+        assert!(i64.check(&[json!(0), json!(u64::MAX)]).is_err());
+        let f32 = lookup("f32").unwrap();
+        assert!(f32.check(&[json!(0), json!(1e39)]).is_err());
+
+        let weighted = lookup("weighted").unwrap();
+        assert!(weighted.check(&[]).is_err());
+        assert!(weighted.check(&[json!(["a", 1]), json!("b")]).is_err());
+
+        assert!(lookup("name").unwrap().check(&[json!(5)]).is_err());
+        assert!(
+            lookup("datetime")
+                .unwrap()
+                .check(&[json!(null), json!("now"), json!("unix")])
+                .is_ok()
+        );
+        assert!(lookup("emial").is_none());
+    }
+}
@@ -0,0 +1,269 @@
+use crate::error::Result;
+use crate::output::{RecordWriter, column_name, column_value, schema_columns};
+use serde_json::Value;
+use std::io::Write;
+
+/// When CSV fields are wrapped in quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// Only fields containing the delimiter, a quote or a line break
+    Necessary,
+    /// Every field
+    Always,
+    /// Every field that isn't a number or boolean
+    NonNumeric,
+    /// Never; fields are written verbatim
+    Never,
+}
+
+/// How array values are written to a single CSV row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArrayHandling {
+    /// Elements joined into one field with the given separator
+    Join(String),
+    /// One row per element. Multiple array columns are exploded side by side,
+    /// and other columns repeat on every row.
+    Explode,
+    /// The whole array as a JSON string
+    Json,
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    pub quote_style: QuoteStyle,
+    pub header: bool,
+    pub arrays: ArrayHandling,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote_style: QuoteStyle::Necessary,
+            header: true,
+            arrays: ArrayHandling::Json,
+        }
+    }
+}
+
+/// Writes records as CSV with nested objects flattened into dotted columns.
+///
+/// Columns come from the schema rather than the records, so every row has the
+/// same columns even when a record is missing a key.
+pub struct CsvWriter<W: Write> {
+    writer: W,
+    columns: Vec<Vec<String>>,
+    options: CsvOptions,
+    header_written: bool,
+}
+
+impl<W: Write> CsvWriter<W> {
+    /// Creates a writer with the columns of `schema` (the config's schema).
+    pub fn new(writer: W, schema: &Value, options: CsvOptions) -> Self {
+        Self {
+            writer,
+            columns: schema_columns(schema),
+            options,
+            header_written: false,
+        }
+    }
+
+    /// The flattened column names, e.g. `user.address.city`.
+    pub fn columns(&self) -> Vec<String> {
+        self.columns.iter().map(|path| column_name(path)).collect()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        if self.header_written || !self.options.header {
+            return Ok(());
+        }
+        self.header_written = true;
+        let names: Vec<Value> = self.columns().into_iter().map(Value::String).collect();
+        let cells: Vec<Option<&Value>> = names.iter().map(Some).collect();
+        self.write_row(&cells)
+    }
+
+    fn write_row(&mut self, cells: &[Option<&Value>]) -> Result<()> {
+        let mut line = Vec::new();
+        for (i, cell) in cells.iter().enumerate() {
+            if i > 0 {
+                line.push(self.options.delimiter);
+            }
+            if let Some(value) = cell {
+                self.push_field(&mut line, value);
+            }
+        }
+        line.extend_from_slice(b"\r\n");
+        self.writer.write_all(&line)?;
+        Ok(())
+    }
+
+    fn push_field(&self, line: &mut Vec<u8>, value: &Value) {
+        let text = match value {
+            Value::Array(items) => match &self.options.arrays {
+                ArrayHandling::Join(separator) => items
+                    .iter()
+                    .map(Self::field_text)
+                    .collect::<Vec<_>>()
+                    .join(separator),
+                _ => value.to_string(),
+            },
+            other => Self::field_text(other),
+        };
+
+        let quote = match self.options.quote_style {
+            QuoteStyle::Always => true,
+            QuoteStyle::Never => false,
+            QuoteStyle::NonNumeric => !matches!(value, Value::Number(_) | Value::Bool(_)),
+            QuoteStyle::Necessary => text
+                .bytes()
+                .any(|b| b == self.options.delimiter || matches!(b, b'"' | b'\r' | b'\n')),
+        };
+
+        if quote {
+            line.push(b'"');
+            line.extend_from_slice(text.replace('"', "\"\"").as_bytes());
+            line.push(b'"');
+        } else {
+            line.extend_from_slice(text.as_bytes());
+        }
+    }
+
+    fn field_text(value: &Value) -> String {
+        match value {
+            Value::Null => String::new(),
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        }
+    }
+}
+
+impl<W: Write> RecordWriter for CsvWriter<W> {
+    fn write_record(&mut self, record: &Value) -> Result<()> {
+        self.write_header()?;
+
+        let cells: Vec<Option<&Value>> = self
+            .columns
+            .iter()
+            .map(|path| column_value(record, path))
+            .collect();
+
+        if self.options.arrays != ArrayHandling::Explode {
+            return self.write_row(&cells);
+        }
+
+        // Arrays are laid out side by side; a record with only empty arrays
+        // still produces a row
+        let rows = cells
+            .iter()
+            .filter_map(|cell| cell.and_then(Value::as_array).map(Vec::len))
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        for row in 0..rows {
+            let exploded: Vec<Option<&Value>> = cells
+                .iter()
+                .map(|cell| match cell {
+                    Some(Value::Array(items)) => items.get(row),
+                    other => *other,
+                })
+                .collect();
+            self.write_row(&exploded)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.write_header()?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write(schema: &Value, records: &[Value], options: CsvOptions) -> String {
+        let mut writer = CsvWriter::new(Vec::new(), schema, options);
+        for record in records {
+            writer.write_record(record).unwrap();
+        }
+        writer.finish().unwrap();
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_flattened_columns_and_quoting() {
+        let schema = json!({
+            "id": {"fake": ["u32"]},
+            "user": {"name": {"fake": ["name"]}, "bio": {"fake": ["sentence"]}}
+        });
+        let records = [
+            json!({"id": 1, "user": {"name": "Smith, Jane", "bio": "Says \"hi\""}}),
+            json!({"id": 2, "user": {"name": "Bob"}}),
+        ];
+
+        let output = write(&schema, &records, CsvOptions::default());
+        assert_eq!(
+            output,
+            "id,user.bio,user.name\r\n1,\"Says \"\"hi\"\"\",\"Smith, Jane\"\r\n2,,Bob\r\n"
+        );
+    }
+
+    #[test]
+    fn test_delimiter_header_and_quote_style() {
+        let schema = json!({"id": {"fake": ["u32"]}, "name": {"fake": ["name"]}});
+        let records = [json!({"id": 7, "name": "Ann"})];
+
+        let options = CsvOptions {
+            delimiter: b';',
+            quote_style: QuoteStyle::NonNumeric,
+            header: false,
+            ..CsvOptions::default()
+        };
+        assert_eq!(write(&schema, &records, options), "7;\"Ann\"\r\n");
+
+        // A header is still written when there are no records
+        assert_eq!(write(&schema, &[], CsvOptions::default()), "id,name\r\n");
+    }
+
+    #[test]
+    fn test_array_handling() {
+        let schema = json!({
+            "id": {"fake": ["u32"]},
+            "tags": {"repeat": [2, {"fake": ["word"]}]}
+        });
+        let records = [
+            json!({"id": 1, "tags": ["a", "b"]}),
+            json!({"id": 2, "tags": []}),
+        ];
+
+        let json_output = write(&schema, &records, CsvOptions::default());
+        assert_eq!(
+            json_output,
+            "id,tags\r\n1,\"[\"\"a\"\",\"\"b\"\"]\"\r\n2,[]\r\n"
+        );
+
+        let join = CsvOptions {
+            arrays: ArrayHandling::Join("|".to_string()),
+            ..CsvOptions::default()
+        };
+        assert_eq!(write(&schema, &records, join), "id,tags\r\n1,a|b\r\n2,\r\n");
+
+        let explode = CsvOptions {
+            arrays: ArrayHandling::Explode,
+            ..CsvOptions::default()
+        };
+        assert_eq!(
+            write(&schema, &records, explode),
+            "id,tags\r\n1,a\r\n1,b\r\n2,\r\n"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use crate::error::Result;
+use crate::output::RecordWriter;
+use serde_json::Value;
+use std::io::Write;
+
+/// Writes newline-delimited JSON, one compact record per line.
+pub struct NdjsonWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> RecordWriter for NdjsonWriter<W> {
+    fn write_record(&mut self, record: &Value) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes all records as a single pretty-printed JSON array.
+pub struct JsonArrayWriter<W: Write> {
+    writer: W,
+    written: usize,
+}
+
+impl<W: Write> JsonArrayWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer, written: 0 }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> RecordWriter for JsonArrayWriter<W> {
+    fn write_record(&mut self, record: &Value) -> Result<()> {
+        let separator: &[u8] = if self.written == 0 { b"[\n" } else { b",\n" };
+        self.writer.write_all(separator)?;
+        serde_json::to_writer_pretty(&mut self.writer, record)?;
+        self.written += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let closing: &[u8] = if self.written == 0 { b"[]\n" } else { b"\n]\n" };
+        self.writer.write_all(closing)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_ndjson_writer() {
+        let mut writer = NdjsonWriter::new(Vec::new());
+        writer.write_record(&json!({"id": 1})).unwrap();
+        writer.write_record(&json!({"id": 2})).unwrap();
+        writer.finish().unwrap();
+
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(output, "{\"id\":1}\n{\"id\":2}\n");
+    }
+
+    #[test]
+    fn test_json_array_writer() {
+        let mut writer = JsonArrayWriter::new(Vec::new());
+        writer.write_record(&json!({"id": 1})).unwrap();
+        writer.write_record(&json!({"id": 2})).unwrap();
+        writer.finish().unwrap();
+
+        let output: Value = serde_json::from_slice(&writer.into_inner()).unwrap();
+        assert_eq!(output, json!([{"id": 1}, {"id": 2}]));
+
+        let mut empty = JsonArrayWriter::new(Vec::new());
+        empty.finish().unwrap();
+        let output: Value = serde_json::from_slice(&empty.into_inner()).unwrap();
+        assert_eq!(output, json!([]));
+    }
+}
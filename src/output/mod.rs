@@ -0,0 +1,131 @@
+pub mod csv;
+pub mod json;
+pub mod sql;
+
+pub use csv::{ArrayHandling, CsvOptions, CsvWriter, QuoteStyle};
+pub use json::{JsonArrayWriter, NdjsonWriter};
+pub use sql::{SqlDialect, SqlOptions, SqlStatement, SqlWriter};
+
+use crate::error::Result;
+use crate::plan::PlanNode;
+use serde_json::Value;
+
+/// A destination that generated records are written to one at a time.
+///
+/// Writers only hold what they need for the current record, so a stream of any
+/// length can be written with bounded memory.
+pub trait RecordWriter {
+    fn write_record(&mut self, record: &Value) -> Result<()>;
+
+    /// Writes any trailing output and flushes the underlying writer.
+    fn finish(&mut self) -> Result<()>;
+}
+
+impl<W: RecordWriter + ?Sized> RecordWriter for Box<W> {
+    fn write_record(&mut self, record: &Value) -> Result<()> {
+        (**self).write_record(record)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        (**self).finish()
+    }
+}
+
+/// Flattened column paths of the records a schema produces.
+///
+/// Object templates are walked into, so `{"user": {"email": ...}}` yields the
+/// path `["user", "email"]`. Expressions, arrays and literals are single columns.
+/// A schema that isn't an object template yields one empty path (the whole record).
+pub(crate) fn schema_columns(schema: &Value) -> Vec<Vec<String>> {
+    let mut columns = Vec::new();
+    collect_columns(schema, &mut Vec::new(), &mut columns);
+    columns
+}
+
+fn collect_columns(schema: &Value, path: &mut Vec<String>, columns: &mut Vec<Vec<String>>) {
+    if let Some(template) = PlanNode::wrapped_template(schema) {
+        return collect_columns(template, path, columns);
+    }
+    match schema {
+        Value::Object(obj) if !is_expression(schema) && !obj.is_empty() => {
+            for (key, value) in obj {
+                path.push(key.clone());
+                collect_columns(value, path, columns);
+                path.pop();
+            }
+        }
+        _ => columns.push(path.clone()),
+    }
+}
+
+fn is_expression(schema: &Value) -> bool {
+    match schema {
+        Value::Object(obj) if obj.len() == 1 => {
+            obj.keys().all(|key| PlanNode::is_expression_key(key))
+        }
+        _ => false,
+    }
+}
+
+/// Name of a flattened column, e.g. `user.email`.
+pub(crate) fn column_name(path: &[String]) -> String {
+    if path.is_empty() {
+        "value".to_string()
+    } else {
+        path.join(".")
+    }
+}
+
+/// Looks up a flattened column in a record.
+pub(crate) fn column_value<'a>(record: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(record, |value, key| value.get(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_columns() {
+        let schema = json!({
+            "id": {"fake": ["uuid"]},
+            "user": {
+                "name": {"fake": ["name"]},
+                "address": {"city": {"fake": ["city"]}, "zip": "12345"}
+            },
+            "tags": {"repeat": [3, {"fake": ["word"]}]},
+            "label": {"cat": ["a", "b"]},
+            "extra": {"optional": [0.5, {"note": {"fake": ["word"]}}]}
+        });
+
+        let columns: Vec<String> = schema_columns(&schema)
+            .iter()
+            .map(|path| column_name(path))
+            .collect();
+        assert_eq!(
+            columns,
+            [
+                "extra.note",
+                "id",
+                "label",
+                "tags",
+                "user.address.city",
+                "user.address.zip",
+                "user.name"
+            ]
+        );
+
+        let record = json!({"user": {"address": {"city": "Paris"}}});
+        let path = vec![
+            "user".to_string(),
+            "address".to_string(),
+            "city".to_string(),
+        ];
+        assert_eq!(column_value(&record, &path), Some(&json!("Paris")));
+        assert_eq!(
+            schema_columns(&json!({"fake": ["uuid"]})),
+            vec![Vec::<String>::new()]
+        );
+    }
+}
@@ -0,0 +1,524 @@
+use crate::error::{DataFakeError, Result};
+use crate::operators::datetime;
+use crate::output::{RecordWriter, column_name, column_value, schema_columns};
+use crate::plan::{PlanNode, Sequence};
+use serde_json::Value;
+use std::io::Write;
+
+/// SQL dialects with their own quoting and type names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+/// How rows are loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlStatement {
+    /// Multi-row `INSERT` statements of up to `batch_size` rows each
+    Insert,
+    /// A Postgres `COPY ... FROM stdin` block in text format
+    Copy,
+}
+
+#[derive(Debug, Clone)]
+pub struct SqlOptions {
+    /// Target table, optionally schema-qualified (`public.users`)
+    pub table: String,
+    pub dialect: SqlDialect,
+    pub statement: SqlStatement,
+    /// Rows per `INSERT` statement
+    pub batch_size: usize,
+    /// Emit a `CREATE TABLE` with column types inferred from the schema
+    pub create_table: bool,
+}
+
+impl SqlOptions {
+    /// Postgres `INSERT`s of 100 rows into `table`.
+    pub fn new(table: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            dialect: SqlDialect::Postgres,
+            statement: SqlStatement::Insert,
+            batch_size: 100,
+            create_table: false,
+        }
+    }
+}
+
+/// Column types inferred from the fake method (or literal) that produces a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SqlType {
+    SmallInt,
+    Integer,
+    BigInt,
+    /// Integers beyond `BIGINT`, i.e. `u64`
+    Decimal,
+    Real,
+    Double,
+    Boolean,
+    Uuid,
+    Timestamp,
+    Date,
+    Time,
+    Text,
+    Json,
+}
+
+impl SqlType {
+    fn infer(schema: Option<&Value>) -> Self {
+        let Some(schema) = schema else {
+            return SqlType::Text;
+        };
+        if let Some(template) = PlanNode::wrapped_template(schema) {
+            return Self::infer(Some(template));
+        }
+        match schema {
+            Value::Bool(_) => SqlType::Boolean,
+            Value::Number(n) if n.is_i64() => SqlType::BigInt,
+            Value::Number(n) if n.is_u64() => SqlType::Decimal,
+            Value::Number(_) => SqlType::Double,
+            Value::Array(_) | Value::Object(_) if is_array_construct(schema) => SqlType::Json,
+            Value::Object(obj) if obj.len() == 1 && obj.contains_key("sequence") => {
+                match Sequence::parse(&obj["sequence"]) {
+                    Ok(sequence) if sequence.is_numeric() => SqlType::BigInt,
+                    _ => SqlType::Text,
+                }
+            }
+            Value::Object(obj) => match obj.get("fake") {
+                Some(Value::Array(args)) => Self::from_method(args),
+                Some(method) => Self::from_method(std::slice::from_ref(method)),
+                None => SqlType::Text,
+            },
+            _ => SqlType::Text,
+        }
+    }
+
+    fn from_method(args: &[Value]) -> Self {
+        let method = args.first().and_then(Value::as_str).unwrap_or_default();
+        if matches!(method, "datetime" | "iso8601_datetime" | "date") {
+            // Unix epochs are integers, and custom strftime formats are text the
+            // database may not parse as a date
+            let schema = datetime::json_schema(args);
+            return match (schema["type"].as_str(), schema["format"].as_str()) {
+                (Some("integer"), _) => SqlType::BigInt,
+                (_, Some("date-time")) => SqlType::Timestamp,
+                (_, Some("date")) => SqlType::Date,
+                _ => SqlType::Text,
+            };
+        }
+        match method {
+            "u8" | "i8" | "i16" => SqlType::SmallInt,
+            "u16" | "i32" => SqlType::Integer,
+            "u32" | "i64" => SqlType::BigInt,
+            "u64" => SqlType::Decimal,
+            "f32" => SqlType::Real,
+            "f64" | "latitude" | "longitude" => SqlType::Double,
+            "bool" | "boolean" => SqlType::Boolean,
+            "uuid" => SqlType::Uuid,
+            "time" => SqlType::Time,
+            _ => SqlType::Text,
+        }
+    }
+
+    fn name(self, dialect: SqlDialect) -> &'static str {
+        match dialect {
+            SqlDialect::Postgres => match self {
+                SqlType::SmallInt => "SMALLINT",
+                SqlType::Integer => "INTEGER",
+                SqlType::BigInt => "BIGINT",
+                SqlType::Decimal => "NUMERIC(20)",
+                SqlType::Real => "REAL",
+                SqlType::Double => "DOUBLE PRECISION",
+                SqlType::Boolean => "BOOLEAN",
+                SqlType::Uuid => "UUID",
+                SqlType::Timestamp => "TIMESTAMP",
+                SqlType::Date => "DATE",
+                SqlType::Time => "TIME",
+                SqlType::Text => "TEXT",
+                SqlType::Json => "JSONB",
+            },
+            SqlDialect::MySql => match self {
+                SqlType::SmallInt => "SMALLINT",
+                SqlType::Integer => "INT",
+                SqlType::BigInt => "BIGINT",
+                SqlType::Decimal => "DECIMAL(20)",
+                SqlType::Real => "FLOAT",
+                SqlType::Double => "DOUBLE",
+                SqlType::Boolean => "BOOLEAN",
+                SqlType::Uuid => "CHAR(36)",
+                // MySQL's TIMESTAMP stops at 2038
+                SqlType::Timestamp => "DATETIME",
+                SqlType::Date => "DATE",
+                SqlType::Time => "TIME",
+                SqlType::Text => "TEXT",
+                SqlType::Json => "JSON",
+            },
+            SqlDialect::Sqlite => match self {
+                SqlType::SmallInt | SqlType::Integer | SqlType::BigInt | SqlType::Boolean => {
+                    "INTEGER"
+                }
+                SqlType::Decimal => "NUMERIC",
+                SqlType::Real | SqlType::Double => "REAL",
+                SqlType::Uuid
+                | SqlType::Timestamp
+                | SqlType::Date
+                | SqlType::Time
+                | SqlType::Text
+                | SqlType::Json => "TEXT",
+            },
+        }
+    }
+}
+
+fn is_array_construct(schema: &Value) -> bool {
+    match schema {
+        Value::Array(_) => true,
+        Value::Object(obj) => obj.is_empty() || (obj.len() == 1 && obj.contains_key("repeat")),
+        _ => false,
+    }
+}
+
+/// Writes records as SQL that loads them into a table.
+///
+/// Nested objects are flattened into dotted column names (`"user.email"`) as for
+/// CSV, and arrays are stored as JSON text.
+pub struct SqlWriter<W: Write> {
+    writer: W,
+    columns: Vec<Vec<String>>,
+    types: Vec<SqlType>,
+    options: SqlOptions,
+    pending: Vec<String>,
+    started: bool,
+}
+
+impl<W: Write> SqlWriter<W> {
+    /// Creates a writer for the columns of `schema` (the config's schema).
+    pub fn new(writer: W, schema: &Value, options: SqlOptions) -> Result<Self> {
+        if options.table.trim().is_empty() {
+            return Err(DataFakeError::InvalidConfig(
+                "SQL output requires a table name".to_string(),
+            ));
+        }
+        if options.batch_size == 0 {
+            return Err(DataFakeError::InvalidConfig(
+                "SQL batch size must be at least 1".to_string(),
+            ));
+        }
+        if options.statement == SqlStatement::Copy && options.dialect != SqlDialect::Postgres {
+            return Err(DataFakeError::InvalidConfig(
+                "COPY output is only supported for Postgres".to_string(),
+            ));
+        }
+
+        let columns = schema_columns(schema);
+        let types = columns
+            .iter()
+            .map(|path| SqlType::infer(column_value(schema, path)))
+            .collect();
+        Ok(Self {
+            writer,
+            columns,
+            types,
+            options,
+            pending: Vec::new(),
+            started: false,
+        })
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        match self.options.dialect {
+            SqlDialect::MySql => format!("`{}`", name.replace('`', "``")),
+            SqlDialect::Postgres | SqlDialect::Sqlite => {
+                format!("\"{}\"", name.replace('"', "\"\""))
+            }
+        }
+    }
+
+    fn table_name(&self) -> String {
+        self.options
+            .table
+            .split('.')
+            .map(|part| self.quote_identifier(part))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    fn column_list(&self) -> String {
+        self.columns
+            .iter()
+            .map(|path| self.quote_identifier(&column_name(path)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Writes the `CREATE TABLE` and `COPY` header, once, before the first row.
+    fn start(&mut self) -> Result<()> {
+        if self.started {
+            return Ok(());
+        }
+        self.started = true;
+
+        let mut header = String::new();
+        if self.options.create_table {
+            let definitions: Vec<String> = self
+                .columns
+                .iter()
+                .zip(&self.types)
+                .map(|(path, ty)| {
+                    format!(
+                        "    {} {}",
+                        self.quote_identifier(&column_name(path)),
+                        ty.name(self.options.dialect)
+                    )
+                })
+                .collect();
+            header.push_str(&format!(
+                "CREATE TABLE {} (\n{}\n);\n",
+                self.table_name(),
+                definitions.join(",\n")
+            ));
+        }
+        if self.options.statement == SqlStatement::Copy {
+            header.push_str(&format!(
+                "COPY {} ({}) FROM stdin;\n",
+                self.table_name(),
+                self.column_list()
+            ));
+        }
+        self.writer.write_all(header.as_bytes())?;
+        Ok(())
+    }
+
+    fn literal(&self, value: Option<&Value>) -> String {
+        match value {
+            None | Some(Value::Null) => "NULL".to_string(),
+            Some(Value::Bool(b)) => match self.options.dialect {
+                SqlDialect::Sqlite => u8::from(*b).to_string(),
+                SqlDialect::Postgres | SqlDialect::MySql => {
+                    if *b { "TRUE" } else { "FALSE" }.to_string()
+                }
+            },
+            Some(Value::Number(n)) => n.to_string(),
+            Some(Value::String(s)) => self.string_literal(s),
+            Some(other) => self.string_literal(&other.to_string()),
+        }
+    }
+
+    fn string_literal(&self, s: &str) -> String {
+        let escaped = s.replace('\'', "''");
+        match self.options.dialect {
+            // Backslash is an escape character in MySQL's default mode
+            SqlDialect::MySql => format!("'{}'", escaped.replace('\\', "\\\\")),
+            SqlDialect::Postgres | SqlDialect::Sqlite => format!("'{escaped}'"),
+        }
+    }
+
+    fn copy_field(value: Option<&Value>) -> String {
+        let text = match value {
+            None | Some(Value::Null) => return "\\N".to_string(),
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+        };
+        text.replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r")
+    }
+
+    fn flush_inserts(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let statement = format!(
+            "INSERT INTO {} ({}) VALUES\n{};\n",
+            self.table_name(),
+            self.column_list(),
+            self.pending.join(",\n")
+        );
+        self.pending.clear();
+        self.writer.write_all(statement.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl<W: Write> RecordWriter for SqlWriter<W> {
+    fn write_record(&mut self, record: &Value) -> Result<()> {
+        self.start()?;
+        let cells = self.columns.iter().map(|path| column_value(record, path));
+
+        match self.options.statement {
+            SqlStatement::Copy => {
+                let row: Vec<String> = cells.map(Self::copy_field).collect();
+                writeln!(self.writer, "{}", row.join("\t"))?;
+            }
+            SqlStatement::Insert => {
+                let row: Vec<String> = cells.map(|value| self.literal(value)).collect();
+                self.pending.push(format!("({})", row.join(", ")));
+                if self.pending.len() >= self.options.batch_size {
+                    self.flush_inserts()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.start()?;
+        match self.options.statement {
+            SqlStatement::Copy => self.writer.write_all(b"\\.\n")?,
+            SqlStatement::Insert => self.flush_inserts()?,
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write(records: &[Value], options: SqlOptions) -> String {
+        let schema = json!({
+            "id": {"unique": {"fake": ["uuid"]}},
+            "age": {"fake": ["u8", 18, 65]},
+            "active": {"fake": ["bool"]},
+            "user": {"name": {"fake": ["name"]}},
+            "tags": {"repeat": [2, {"fake": ["word"]}]}
+        });
+        let mut writer = SqlWriter::new(Vec::new(), &schema, options).unwrap();
+        for record in records {
+            writer.write_record(record).unwrap();
+        }
+        writer.finish().unwrap();
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    fn records() -> Vec<Value> {
+        vec![
+            json!({"id": "a1", "age": 30, "active": true, "user": {"name": "O'Brien"}, "tags": ["x"]}),
+            json!({"id": "b2", "age": 41, "active": false, "user": {"name": "C:\\dir"}, "tags": []}),
+            json!({"id": "c3", "age": 52, "active": true, "tags": []}),
+        ]
+    }
+
+    #[test]
+    fn test_batched_inserts() {
+        let options = SqlOptions {
+            batch_size: 2,
+            ..SqlOptions::new("users")
+        };
+        let output = write(&records(), options);
+
+        assert_eq!(output.matches("INSERT INTO \"users\"").count(), 2);
+        assert!(output.starts_with(
+            "INSERT INTO \"users\" (\"active\", \"age\", \"id\", \"tags\", \"user.name\") VALUES\n\
+             (TRUE, 30, 'a1', '[\"x\"]', 'O''Brien'),\n"
+        ));
+        assert!(output.contains("(FALSE, 41, 'b2', '[]', 'C:\\dir');\n"));
+        assert!(output.ends_with("(TRUE, 52, 'c3', '[]', NULL);\n"));
+    }
+
+    #[test]
+    fn test_dialect_escaping() {
+        let mysql = write(
+            &records()[1..2],
+            SqlOptions {
+                dialect: SqlDialect::MySql,
+                ..SqlOptions::new("app.users")
+            },
+        );
+        assert!(mysql.starts_with("INSERT INTO `app`.`users` (`active`, "));
+        assert!(mysql.contains("'C:\\\\dir'"));
+
+        let sqlite = write(
+            &records()[..1],
+            SqlOptions {
+                dialect: SqlDialect::Sqlite,
+                ..SqlOptions::new("users")
+            },
+        );
+        assert!(sqlite.contains("(1, 30, 'a1'"));
+    }
+
+    #[test]
+    fn test_create_table_types() {
+        let options = SqlOptions {
+            create_table: true,
+            ..SqlOptions::new("users")
+        };
+        let output = write(&[], options);
+        assert_eq!(
+            output,
+            "CREATE TABLE \"users\" (\n    \"active\" BOOLEAN,\n    \"age\" SMALLINT,\n    \
+             \"id\" UUID,\n    \"tags\" JSONB,\n    \"user.name\" TEXT\n);\n"
+        );
+
+        let options = SqlOptions {
+            dialect: SqlDialect::Sqlite,
+            create_table: true,
+            ..SqlOptions::new("users")
+        };
+        assert!(write(&[], options).contains("\"id\" TEXT"));
+
+        let infer = |fake: Value| SqlType::infer(Some(&json!({ "fake": fake })));
+        assert_eq!(
+            infer(json!(["datetime", "-30d", "now"])),
+            SqlType::Timestamp
+        );
+        assert_eq!(
+            infer(json!(["datetime", "-30d", "now", "unix_ms"])),
+            SqlType::BigInt
+        );
+        assert_eq!(infer(json!(["date"])), SqlType::Date);
+        assert_eq!(
+            infer(json!(["date", null, null, "%d/%m/%Y"])),
+            SqlType::Text
+        );
+        assert_eq!(infer(json!(["date", "%d/%m/%Y"])), SqlType::Text);
+        assert_eq!(
+            infer(json!(["datetime", null, null, "%H:%M on %d %b"])),
+            SqlType::Text
+        );
+        assert_eq!(infer(json!(["boolean"])), SqlType::Boolean);
+
+        let sequence = |options: Value| SqlType::infer(Some(&json!({ "sequence": options })));
+        assert_eq!(sequence(json!({"start": 1000})), SqlType::BigInt);
+        assert_eq!(sequence(json!({"prefix": "INV-"})), SqlType::Text);
+    }
+
+    #[test]
+    fn test_postgres_copy() {
+        let options = SqlOptions {
+            statement: SqlStatement::Copy,
+            ..SqlOptions::new("users")
+        };
+        let output = write(&records(), options);
+        assert_eq!(
+            output,
+            "COPY \"users\" (\"active\", \"age\", \"id\", \"tags\", \"user.name\") FROM stdin;\n\
+             true\t30\ta1\t[\"x\"]\tO'Brien\n\
+             false\t41\tb2\t[]\tC:\\\\dir\n\
+             true\t52\tc3\t[]\t\\N\n\
+             \\.\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let schema = json!({"id": {"fake": ["uuid"]}});
+        let copy_mysql = SqlOptions {
+            dialect: SqlDialect::MySql,
+            statement: SqlStatement::Copy,
+            ..SqlOptions::new("users")
+        };
+        assert!(SqlWriter::new(Vec::new(), &schema, copy_mysql).is_err());
+        assert!(SqlWriter::new(Vec::new(), &schema, SqlOptions::new("")).is_err());
+    }
+}
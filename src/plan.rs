@@ -0,0 +1,1205 @@
+use crate::engine::Engine;
+use crate::error::{DataFakeError, Result};
+use crate::locale::Locale;
+use crate::operators::datetime::{self, DateTimeRange};
+use crate::operators::fake::LOCALIZED_METHODS;
+use crate::operators::{FakeOperator, RegexGenerator};
+use crate::rng;
+use crate::types::DataFakeConfig;
+use chrono::{DateTime, Utc};
+use datalogic_rs::{CompiledLogic, CompiledNode};
+use rand::Rng;
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Variable holding the element index inside a `repeat` template.
+pub const INDEX_VARIABLE: &str = "$index";
+
+/// Variable holding the index of the record being generated.
+pub const RECORD_VARIABLE: &str = "$record";
+
+/// Schema constructs handled by the plan itself rather than by datalogic-rs.
+const SCHEMA_CONSTRUCTS: &[&str] = &[
+    "repeat", "unique", "optional", "nullable", "sequence", "ref", "one_of",
+];
+
+/// Attempts a `unique` field makes before giving up, unless configured.
+pub const DEFAULT_UNIQUE_ATTEMPTS: usize = 100;
+
+/// A schema node compiled ahead of generation.
+///
+/// Compiling walks the schema once, so generating a record only executes the
+/// resulting tree instead of recompiling every JSONLogic expression.
+#[derive(Debug, Clone)]
+pub(crate) enum PlanNode {
+    /// Value that is identical for every record (plain JSON or constant logic)
+    Literal(Value),
+    /// `fake` call, dispatched straight to the fake operator
+    Fake(Vec<Value>),
+    /// `fake` regex call, with the pattern parsed once
+    Regex(RegexGenerator),
+    /// `fake` datetime call, with its range and format parsed once
+    DateTime(DateTimeRange),
+    /// Any other JSONLogic expression, evaluated against the record's variables
+    Logic(Arc<CompiledLogic>),
+    Object(Vec<(String, PlanNode)>),
+    Array(Vec<PlanNode>),
+    /// `repeat`: the item template evaluated a random number of times in `min..=max`
+    Repeat {
+        min: usize,
+        max: usize,
+        item: Box<PlanNode>,
+    },
+    /// `unique`: the item re-generated until it produces a value not emitted before
+    Unique {
+        item: Box<PlanNode>,
+        max_attempts: usize,
+        /// Compact JSON of the template, for error messages
+        template: String,
+        /// Values emitted so far, as JSON text
+        seen: Arc<Mutex<HashSet<String>>>,
+    },
+    /// `optional`: the item, or with the given probability no value at all, so
+    /// the enclosing object key or array element is dropped
+    Optional {
+        probability: f64,
+        item: Box<PlanNode>,
+    },
+    /// `nullable`: the item, or with the given probability `null`
+    Nullable {
+        probability: f64,
+        item: Box<PlanNode>,
+    },
+    /// `one_of`: one of several templates, picked at random
+    OneOf(Vec<PlanNode>),
+    /// `sequence`: an auto-incrementing number, optionally formatted as text
+    Sequence(Sequence),
+    /// `ref`: a primary key of another entity of a dataset
+    Reference {
+        entity: String,
+        mode: ReferenceMode,
+        /// The referenced entity's keys, bound once its records are generated
+        keys: Arc<RwLock<Arc<Vec<Value>>>>,
+    },
+}
+
+/// How a `ref` picks among the referenced entity's keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReferenceMode {
+    /// Any key, drawn at random
+    Random,
+    /// Key `n % count` for record `n`, so every key is used evenly
+    RoundRobin,
+}
+
+/// The `start`, `step` and counter of a named counter, keyed by its name.
+type SharedCounter = (i64, i64, Arc<AtomicU64>);
+
+/// What advances a `sequence`.
+#[derive(Debug, Clone)]
+pub(crate) enum SequenceScope {
+    /// The index of the record, so record `n` always gets the `n`th value
+    Record,
+    /// The element index of the enclosing `repeat`
+    Item,
+    /// A counter shared by every sequence with the same name, advanced each time
+    /// one of them is generated
+    Counter { name: String, next: Arc<AtomicU64> },
+}
+
+/// A parsed `{"sequence": {...}}`: values `start`, `start + step`, ... with
+/// digits zero-padded to `pad` and wrapped in `prefix` and `suffix`.
+#[derive(Debug, Clone)]
+pub(crate) struct Sequence {
+    pub(crate) start: i64,
+    pub(crate) step: i64,
+    pub(crate) pad: usize,
+    pub(crate) prefix: String,
+    pub(crate) suffix: String,
+    scope: SequenceScope,
+}
+
+impl Sequence {
+    pub(crate) fn parse(args: &Value) -> Result<Self> {
+        let invalid = |message: String| DataFakeError::InvalidConfig(format!("sequence {message}"));
+        let Value::Object(options) = args else {
+            return Err(invalid(format!("requires an options object, got {args}")));
+        };
+
+        let integer = |key: &str, default: i64| match options.get(key) {
+            None => Ok(default),
+            Some(value) => value
+                .as_i64()
+                .ok_or_else(|| invalid(format!("{key} must be an integer, got {value}"))),
+        };
+        let text = |key: &str| match options.get(key) {
+            None => Ok(String::new()),
+            Some(Value::String(text)) => Ok(text.clone()),
+            Some(value) => Err(invalid(format!("{key} must be a string, got {value}"))),
+        };
+
+        let pad = match options.get("pad") {
+            None => 0,
+            Some(value) => value
+                .as_u64()
+                .filter(|pad| *pad <= 32)
+                .ok_or_else(|| invalid(format!("pad must be an integer 0-32, got {value}")))?
+                as usize,
+        };
+        let scope = match (options.get("per"), options.get("counter")) {
+            (None, None) => SequenceScope::Record,
+            (Some(per), None) => match per.as_str() {
+                Some("record") => SequenceScope::Record,
+                Some("item") => SequenceScope::Item,
+                _ => {
+                    return Err(invalid(format!(
+                        "per must be \"record\" or \"item\", got {per}"
+                    )));
+                }
+            },
+            (None, Some(Value::String(name))) if !name.is_empty() => SequenceScope::Counter {
+                name: name.clone(),
+                next: Arc::default(),
+            },
+            (None, Some(name)) => {
+                return Err(invalid(format!(
+                    "counter must be a non-empty string, got {name}"
+                )));
+            }
+            (Some(_), Some(_)) => {
+                return Err(invalid("takes either per or counter, not both".to_string()));
+            }
+        };
+        if let Some(key) = options.keys().find(|key| {
+            !matches!(
+                key.as_str(),
+                "start" | "step" | "pad" | "prefix" | "suffix" | "per" | "counter"
+            )
+        }) {
+            return Err(invalid(format!("has unknown option '{key}'")));
+        }
+
+        Ok(Self {
+            start: integer("start", 1)?,
+            step: integer("step", 1)?,
+            pad,
+            prefix: text("prefix")?,
+            suffix: text("suffix")?,
+            scope,
+        })
+    }
+
+    /// Whether values are plain numbers rather than formatted text.
+    pub(crate) fn is_numeric(&self) -> bool {
+        self.pad == 0 && self.prefix.is_empty() && self.suffix.is_empty()
+    }
+
+    /// The `n`th value of the sequence (counting from 0).
+    fn value(&self, n: u64) -> Result<Value> {
+        let value =
+            i64::try_from(self.start as i128 + self.step as i128 * n as i128).map_err(|_| {
+                DataFakeError::InvalidConfig(format!(
+                    "sequence starting at {} with step {} overflowed at position {n}",
+                    self.start, self.step
+                ))
+            })?;
+        if self.is_numeric() {
+            return Ok(Value::from(value));
+        }
+        let sign = if value < 0 { "-" } else { "" };
+        Ok(Value::String(format!(
+            "{}{sign}{:0pad$}{}",
+            self.prefix,
+            value.unsigned_abs(),
+            self.suffix,
+            pad = self.pad
+        )))
+    }
+
+    fn next(&self, data: &Value) -> Result<Value> {
+        let position = match &self.scope {
+            SequenceScope::Record => data
+                .get(RECORD_VARIABLE)
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+            SequenceScope::Item => data
+                .get(INDEX_VARIABLE)
+                .and_then(Value::as_u64)
+                .ok_or_else(|| {
+                    DataFakeError::InvalidConfig(
+                        "sequence with per \"item\" must be inside a repeat".to_string(),
+                    )
+                })?,
+            SequenceScope::Counter { next, .. } => next.fetch_add(1, Ordering::Relaxed),
+        };
+        self.value(position)
+    }
+}
+
+impl PlanNode {
+    pub(crate) fn compile(schema: &Value) -> Result<Self> {
+        match schema {
+            Value::Object(obj) if obj.len() == 1 => {
+                let (key, args) = obj.iter().next().unwrap();
+                if key == "fake" {
+                    // datalogic-rs hands custom operators their raw arguments, so a
+                    // non-array argument is treated as a single-element list
+                    let args = match args {
+                        Value::Array(arr) => arr.clone(),
+                        other => vec![other.clone()],
+                    };
+                    if let [method, Value::String(pattern)] = args.as_slice()
+                        && method == "regex"
+                    {
+                        return Ok(PlanNode::Regex(RegexGenerator::parse(pattern)?));
+                    }
+                    if let Some(Value::String(method)) = args.first()
+                        && matches!(method.as_str(), "datetime" | "iso8601_datetime" | "date")
+                    {
+                        return Ok(PlanNode::DateTime(DateTimeRange::parse(&args)?));
+                    }
+                    return Ok(PlanNode::Fake(args));
+                }
+                if key == "repeat" {
+                    let (count, item) = Self::repeat_args(args)?;
+                    let (min, max) = Self::repeat_bounds(count)?;
+                    return Ok(PlanNode::Repeat {
+                        min,
+                        max,
+                        item: Box::new(Self::compile(item)?),
+                    });
+                }
+                if key == "unique" {
+                    let (item, max_attempts) = Self::unique_args(args)?;
+                    return Ok(PlanNode::Unique {
+                        item: Box::new(Self::compile(item)?),
+                        max_attempts,
+                        template: item.to_string(),
+                        seen: Arc::default(),
+                    });
+                }
+                if key == "optional" || key == "nullable" {
+                    let (probability, item) = Self::probability_args(key, args)?;
+                    let item = Box::new(Self::compile(item)?);
+                    return Ok(if key == "optional" {
+                        PlanNode::Optional { probability, item }
+                    } else {
+                        PlanNode::Nullable { probability, item }
+                    });
+                }
+                if key == "ref" {
+                    let (entity, mode) = Self::reference_args(args)?;
+                    return Ok(PlanNode::Reference {
+                        entity: entity.to_string(),
+                        mode,
+                        keys: Arc::default(),
+                    });
+                }
+                if key == "one_of" {
+                    let items = Self::one_of_args(args)?
+                        .iter()
+                        .map(Self::compile)
+                        .collect::<Result<Vec<_>>>()?;
+                    return Ok(PlanNode::OneOf(items));
+                }
+                if key == "sequence" {
+                    return Ok(PlanNode::Sequence(Sequence::parse(args)?));
+                }
+                if Engine::is_jsonlogic_operator(key) {
+                    let compiled = Engine::compile(schema)?;
+                    // datalogic-rs folds constant expressions at compile time
+                    if let CompiledNode::Value { value } = &compiled.root {
+                        return Ok(PlanNode::Literal(value.clone()));
+                    }
+                    return Ok(PlanNode::Logic(compiled));
+                }
+                Self::compile_object(obj)
+            }
+            Value::Object(obj) => Self::compile_object(obj),
+            Value::Array(arr) => {
+                let items = arr.iter().map(Self::compile).collect::<Result<Vec<_>>>()?;
+                if items.iter().all(PlanNode::is_literal) {
+                    let values = items.into_iter().filter_map(PlanNode::into_literal);
+                    return Ok(PlanNode::Literal(Value::Array(values.collect())));
+                }
+                Ok(PlanNode::Array(items))
+            }
+            _ => Ok(PlanNode::Literal(schema.clone())),
+        }
+    }
+
+    fn compile_object(obj: &Map<String, Value>) -> Result<Self> {
+        let fields = obj
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), Self::compile(value)?)))
+            .collect::<Result<Vec<_>>>()?;
+        if fields.iter().all(|(_, node)| node.is_literal()) {
+            let values = fields
+                .into_iter()
+                .filter_map(|(key, node)| Some((key, node.into_literal()?)));
+            return Ok(PlanNode::Literal(Value::Object(values.collect())));
+        }
+        Ok(PlanNode::Object(fields))
+    }
+
+    /// Whether a single-key object with this key is an expression to evaluate
+    /// rather than an object template.
+    pub(crate) fn is_expression_key(key: &str) -> bool {
+        SCHEMA_CONSTRUCTS.contains(&key) || Engine::is_jsonlogic_operator(key)
+    }
+
+    /// Splits the arguments of `{"repeat": [count, template]}`.
+    pub(crate) fn repeat_args(args: &Value) -> Result<(&Value, &Value)> {
+        match args {
+            Value::Array(arr) if arr.len() == 2 => Ok((&arr[0], &arr[1])),
+            _ => Err(DataFakeError::InvalidConfig(
+                "repeat requires exactly two arguments: a count and an item template".to_string(),
+            )),
+        }
+    }
+
+    /// Parses a repeat count, either a fixed number or a `[min, max]` range.
+    pub(crate) fn repeat_bounds(count: &Value) -> Result<(usize, usize)> {
+        let bound = |value: &Value| {
+            value.as_u64().map(|n| n as usize).ok_or_else(|| {
+                DataFakeError::InvalidConfig(format!(
+                    "repeat count must be a non-negative integer, got {value}"
+                ))
+            })
+        };
+
+        match count {
+            Value::Array(range) if range.len() == 2 => {
+                let (min, max) = (bound(&range[0])?, bound(&range[1])?);
+                if min > max {
+                    return Err(DataFakeError::InvalidRange {
+                        min: min as f64,
+                        max: max as f64,
+                    });
+                }
+                Ok((min, max))
+            }
+            Value::Array(_) => Err(DataFakeError::InvalidConfig(
+                "repeat range must be [min, max]".to_string(),
+            )),
+            other => bound(other).map(|n| (n, n)),
+        }
+    }
+
+    /// Splits `{"unique": template}` or `{"unique": [template, max_attempts]}`.
+    pub(crate) fn unique_args(args: &Value) -> Result<(&Value, usize)> {
+        match args {
+            Value::Array(arr) if arr.len() == 2 && arr[1].is_number() => {
+                let attempts = arr[1].as_u64().filter(|n| *n > 0).ok_or_else(|| {
+                    DataFakeError::InvalidConfig(format!(
+                        "unique attempts must be a positive integer, got {}",
+                        arr[1]
+                    ))
+                })?;
+                Ok((&arr[0], attempts as usize))
+            }
+            template => Ok((template, DEFAULT_UNIQUE_ATTEMPTS)),
+        }
+    }
+
+    /// Splits `{"optional": [probability, template]}` (or `nullable`), checking
+    /// the probability is within `0..=1`.
+    pub(crate) fn probability_args<'a>(key: &str, args: &'a Value) -> Result<(f64, &'a Value)> {
+        match args {
+            Value::Array(arr) if arr.len() == 2 => match arr[0].as_f64() {
+                Some(p) if (0.0..=1.0).contains(&p) => Ok((p, &arr[1])),
+                _ => Err(DataFakeError::InvalidConfig(format!(
+                    "{key} probability must be a number between 0 and 1, got {}",
+                    arr[0]
+                ))),
+            },
+            _ => Err(DataFakeError::InvalidConfig(format!(
+                "{key} requires exactly two arguments: a probability and a template"
+            ))),
+        }
+    }
+
+    /// The templates of `{"one_of": [template, ...]}`.
+    pub(crate) fn one_of_args(args: &Value) -> Result<&Vec<Value>> {
+        match args {
+            Value::Array(items) if !items.is_empty() => Ok(items),
+            _ => Err(DataFakeError::InvalidConfig(format!(
+                "one_of requires a non-empty array of templates, got {args}"
+            ))),
+        }
+    }
+
+    /// Splits `{"ref": "entity"}` or `{"ref": ["entity", mode]}`.
+    pub(crate) fn reference_args(args: &Value) -> Result<(&str, ReferenceMode)> {
+        let (entity, mode) = match args {
+            Value::String(entity) => (entity.as_str(), None),
+            Value::Array(arr) if arr.len() == 2 => {
+                (arr[0].as_str().unwrap_or_default(), Some(&arr[1]))
+            }
+            _ => ("", None),
+        };
+        if entity.is_empty() {
+            return Err(DataFakeError::InvalidConfig(format!(
+                "ref requires an entity name or [entity, mode], got {args}"
+            )));
+        }
+        let mode = match mode.map(|mode| (mode, mode.as_str())) {
+            None | Some((_, Some("random"))) => ReferenceMode::Random,
+            Some((_, Some("round_robin"))) => ReferenceMode::RoundRobin,
+            Some((mode, _)) => {
+                return Err(DataFakeError::InvalidConfig(format!(
+                    "ref mode must be \"random\" or \"round_robin\", got {mode}"
+                )));
+            }
+        };
+        Ok((entity, mode))
+    }
+
+    /// Adds the entities referenced by `ref` constructs anywhere in `schema`.
+    pub(crate) fn collect_references<'a>(schema: &'a Value, entities: &mut BTreeSet<&'a str>) {
+        match schema {
+            Value::Object(obj) => {
+                if obj.len() == 1
+                    && let Some(args) = obj.get("ref")
+                    && let Ok((entity, _)) = Self::reference_args(args)
+                {
+                    entities.insert(entity);
+                    return;
+                }
+                obj.values()
+                    .for_each(|value| Self::collect_references(value, entities));
+            }
+            Value::Array(arr) => arr
+                .iter()
+                .for_each(|value| Self::collect_references(value, entities)),
+            _ => {}
+        }
+    }
+
+    /// The template inside a `unique`, `optional` or `nullable` wrapper, which
+    /// produces values of the same shape as the wrapper.
+    pub(crate) fn wrapped_template(schema: &Value) -> Option<&Value> {
+        let Value::Object(obj) = schema else {
+            return None;
+        };
+        if obj.len() != 1 {
+            return None;
+        }
+        let (key, args) = obj.iter().next()?;
+        match key.as_str() {
+            "unique" => Self::unique_args(args).ok().map(|(item, _)| item),
+            "optional" | "nullable" => Self::probability_args(key, args).ok().map(|(_, item)| item),
+            _ => None,
+        }
+    }
+
+    /// Calls `f` on this node and every node below it.
+    fn walk(&self, f: &mut dyn FnMut(&PlanNode)) {
+        f(self);
+        match self {
+            PlanNode::Object(fields) => fields.iter().for_each(|(_, node)| node.walk(f)),
+            PlanNode::Array(items) | PlanNode::OneOf(items) => {
+                items.iter().for_each(|node| node.walk(f))
+            }
+            PlanNode::Repeat { item, .. }
+            | PlanNode::Unique { item, .. }
+            | PlanNode::Optional { item, .. }
+            | PlanNode::Nullable { item, .. } => item.walk(f),
+            PlanNode::Literal(_)
+            | PlanNode::Fake(_)
+            | PlanNode::Regex(_)
+            | PlanNode::DateTime(_)
+            | PlanNode::Logic(_)
+            | PlanNode::Sequence(_)
+            | PlanNode::Reference { .. } => {}
+        }
+    }
+
+    fn walk_mut(&mut self, f: &mut dyn FnMut(&mut PlanNode)) {
+        f(self);
+        match self {
+            PlanNode::Object(fields) => fields.iter_mut().for_each(|(_, node)| node.walk_mut(f)),
+            PlanNode::Array(items) | PlanNode::OneOf(items) => {
+                items.iter_mut().for_each(|node| node.walk_mut(f))
+            }
+            PlanNode::Repeat { item, .. }
+            | PlanNode::Unique { item, .. }
+            | PlanNode::Optional { item, .. }
+            | PlanNode::Nullable { item, .. } => item.walk_mut(f),
+            PlanNode::Literal(_)
+            | PlanNode::Fake(_)
+            | PlanNode::Regex(_)
+            | PlanNode::DateTime(_)
+            | PlanNode::Logic(_)
+            | PlanNode::Sequence(_)
+            | PlanNode::Reference { .. } => {}
+        }
+    }
+
+    /// Whether any node tracks unique values.
+    pub(crate) fn has_unique(&self) -> bool {
+        let mut found = false;
+        self.walk(&mut |node| found |= matches!(node, PlanNode::Unique { .. }));
+        found
+    }
+
+    /// Forgets the values emitted by every `unique` node.
+    pub(crate) fn reset_unique(&self) {
+        self.walk(&mut |node| {
+            if let PlanNode::Unique { seen, .. } = node {
+                seen.lock().unwrap_or_else(|e| e.into_inner()).clear();
+            }
+        });
+    }
+
+    /// Whether any `sequence` advances a named counter.
+    pub(crate) fn has_counters(&self) -> bool {
+        let mut found = false;
+        self.walk(&mut |node| {
+            found |= matches!(
+                node,
+                PlanNode::Sequence(Sequence {
+                    scope: SequenceScope::Counter { .. },
+                    ..
+                })
+            )
+        });
+        found
+    }
+
+    /// Restarts every named counter from its first value.
+    pub(crate) fn reset_counters(&self) {
+        self.walk(&mut |node| {
+            if let PlanNode::Sequence(Sequence {
+                scope: SequenceScope::Counter { next, .. },
+                ..
+            }) = node
+            {
+                next.store(0, Ordering::Relaxed);
+            }
+        });
+    }
+
+    /// Lets every `ref` to `entity` pick from `keys`.
+    fn bind_references(&self, entity: &str, keys: &Arc<Vec<Value>>) {
+        self.walk(&mut |node| {
+            if let PlanNode::Reference {
+                entity: target,
+                keys: bound,
+                ..
+            } = node
+                && target == entity
+            {
+                *bound.write().unwrap_or_else(|e| e.into_inner()) = keys.clone();
+            }
+        });
+    }
+
+    /// Makes sequences with the same counter name share one counter. They must
+    /// agree on `start` and `step`, as they draw from the same values.
+    fn share_counters(&mut self, counters: &mut HashMap<String, SharedCounter>) -> Result<()> {
+        let mut result = Ok(());
+        self.walk_mut(&mut |node| {
+            if let PlanNode::Sequence(Sequence {
+                start,
+                step,
+                scope: SequenceScope::Counter { name, next },
+                ..
+            }) = node
+            {
+                let (first_start, first_step, shared) = counters
+                    .entry(name.clone())
+                    .or_insert_with(|| (*start, *step, next.clone()));
+                if (*first_start, *first_step) != (*start, *step) && result.is_ok() {
+                    result = Err(DataFakeError::InvalidConfig(format!(
+                        "sequences sharing counter '{name}' must have the same start and step, \
+                         got start {first_start} step {first_step} and start {start} step {step}"
+                    )));
+                }
+                *next = shared.clone();
+            }
+        });
+        result
+    }
+
+    fn is_literal(&self) -> bool {
+        matches!(self, PlanNode::Literal(_))
+    }
+
+    fn into_literal(self) -> Option<Value> {
+        match self {
+            PlanNode::Literal(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Executes the node with `data` as the JSONLogic data (the record's variables).
+    ///
+    /// An omitted `optional` value is `null` here; object fields and array
+    /// elements use [`PlanNode::execute_entry`] to drop it instead.
+    pub(crate) fn execute(&self, data: &Arc<Value>) -> Result<Value> {
+        Ok(self.execute_entry(data)?.unwrap_or(Value::Null))
+    }
+
+    /// Executes the node, returning `None` when an `optional` value is omitted.
+    fn execute_entry(&self, data: &Arc<Value>) -> Result<Option<Value>> {
+        let value = match self {
+            PlanNode::Optional { probability, item } => {
+                return if Self::happens(*probability) {
+                    Ok(None)
+                } else {
+                    item.execute_entry(data)
+                };
+            }
+            PlanNode::Nullable { probability, item } => {
+                if Self::happens(*probability) {
+                    Value::Null
+                } else {
+                    item.execute(data)?
+                }
+            }
+            other => other.execute_value(data)?,
+        };
+        Ok(Some(value))
+    }
+
+    /// Draws whether an event with `probability` happens, without consuming
+    /// randomness for the always/never cases.
+    fn happens(probability: f64) -> bool {
+        match probability {
+            p if p <= 0.0 => false,
+            p if p >= 1.0 => true,
+            p => rng::with_rng(|rng| rng.random_bool(p)),
+        }
+    }
+
+    fn execute_value(&self, data: &Arc<Value>) -> Result<Value> {
+        match self {
+            PlanNode::Literal(value) => Ok(value.clone()),
+            PlanNode::Fake(args) => FakeOperator::generate(args),
+            PlanNode::Regex(generator) => {
+                Ok(Value::String(rng::with_rng(|rng| generator.generate(rng))))
+            }
+            PlanNode::DateTime(range) => rng::with_rng(|rng| range.generate(rng)),
+            PlanNode::Logic(compiled) => Engine::evaluate_compiled(compiled, data.clone()),
+            PlanNode::Object(fields) => {
+                let mut result = Map::new();
+                for (key, node) in fields {
+                    if let Some(value) = node.execute_entry(data)? {
+                        result.insert(key.clone(), value);
+                    }
+                }
+                Ok(Value::Object(result))
+            }
+            PlanNode::Array(items) => {
+                let mut result = Vec::with_capacity(items.len());
+                for item in items {
+                    result.extend(item.execute_entry(data)?);
+                }
+                Ok(Value::Array(result))
+            }
+            PlanNode::Repeat { min, max, item } => {
+                let count = if min == max {
+                    *min
+                } else {
+                    rng::with_rng(|rng| rng.random_range(*min..=*max))
+                };
+
+                // Each element sees the record's variables plus its own index
+                let mut scoped = Arc::new(data.as_ref().clone());
+                let mut result = Vec::with_capacity(count);
+                for index in 0..count {
+                    if let Value::Object(variables) = Arc::make_mut(&mut scoped) {
+                        variables.insert(INDEX_VARIABLE.to_string(), Value::from(index));
+                    }
+                    result.extend(item.execute_entry(&scoped)?);
+                }
+                Ok(Value::Array(result))
+            }
+            PlanNode::Unique {
+                item,
+                max_attempts,
+                template,
+                seen,
+            } => {
+                for _ in 0..*max_attempts {
+                    let value = item.execute(data)?;
+                    let mut seen = seen.lock().unwrap_or_else(|e| e.into_inner());
+                    if seen.insert(value.to_string()) {
+                        return Ok(value);
+                    }
+                }
+                Err(DataFakeError::UniqueValuesExhausted {
+                    template: template.clone(),
+                    attempts: *max_attempts,
+                })
+            }
+            PlanNode::OneOf(items) => {
+                let index = rng::with_rng(|rng| rng.random_range(0..items.len()));
+                items[index].execute(data)
+            }
+            PlanNode::Sequence(sequence) => sequence.next(data),
+            PlanNode::Reference { entity, mode, keys } => {
+                let keys = keys.read().unwrap_or_else(|e| e.into_inner());
+                if keys.is_empty() {
+                    return Err(DataFakeError::InvalidConfig(format!(
+                        "ref to '{entity}' has no records to reference; refs are only \
+                         resolved when generating a dataset that includes '{entity}'"
+                    )));
+                }
+                let index = match mode {
+                    ReferenceMode::Random => rng::with_rng(|rng| rng.random_range(0..keys.len())),
+                    ReferenceMode::RoundRobin => {
+                        let record = data.get(RECORD_VARIABLE).and_then(Value::as_u64);
+                        (record.unwrap_or(0) % keys.len() as u64) as usize
+                    }
+                };
+                Ok(keys[index].clone())
+            }
+            PlanNode::Optional { .. } | PlanNode::Nullable { .. } => {
+                Ok(self.execute_entry(data)?.unwrap_or(Value::Null))
+            }
+        }
+    }
+}
+
+/// A configuration compiled once and executed for every generated record.
+#[derive(Debug, Clone)]
+pub struct ExecutionPlan {
+    variables: Vec<(String, PlanNode)>,
+    schema: PlanNode,
+    /// Instant that `now`, `today` and relative datetime bounds are resolved
+    /// against, fixed for every record
+    now: DateTime<Utc>,
+    /// Whether `now` comes from `metadata.now` rather than the clock
+    pinned: bool,
+}
+
+impl ExecutionPlan {
+    pub fn compile(config: &DataFakeConfig) -> Result<Self> {
+        let metadata = config.metadata.as_ref();
+        let locale = match metadata.and_then(|m| m.locale.as_ref()) {
+            Some(code) => Some(code.parse::<Locale>()?),
+            None => None,
+        };
+        let (now, pinned) = match metadata.and_then(|m| m.now.as_deref()) {
+            Some(now) => (datetime::parse_anchor(now)?, true),
+            None if metadata.is_some_and(|m| m.seed.is_some()) => (datetime::seeded_now(), false),
+            None => (Utc::now(), false),
+        };
+        let compile = |value: &Value| match locale {
+            Some(locale) => PlanNode::compile(&Self::with_default_locale(value, locale)),
+            None => PlanNode::compile(value),
+        };
+
+        let mut variables = Engine::sort_variables(&config.variables)?
+            .into_iter()
+            .map(|(name, value)| Ok((name.clone(), compile(value)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let mut schema = compile(&config.schema)?;
+
+        let mut counters = HashMap::new();
+        for (_, node) in &mut variables {
+            node.share_counters(&mut counters)?;
+        }
+        schema.share_counters(&mut counters)?;
+
+        Ok(Self {
+            variables,
+            schema,
+            now,
+            pinned,
+        })
+    }
+
+    /// Anchors datetimes at the fixed seeded epoch, so the same seed gives the
+    /// same output on any day, unless `metadata.now` pins them already.
+    pub(crate) fn seeded(self) -> Self {
+        if self.pinned {
+            return self;
+        }
+        Self {
+            now: datetime::seeded_now(),
+            ..self
+        }
+    }
+
+    /// Adds `locale` to every locale-aware `fake` call that doesn't name one,
+    /// including calls nested inside other JSONLogic operators.
+    fn with_default_locale(value: &Value, locale: Locale) -> Value {
+        match value {
+            Value::Object(obj) => {
+                // A lone method name is shorthand for `["method"]`, as in `compile`
+                let method = match obj.get("fake") {
+                    Some(Value::Array(args)) if args.len() == 1 => args.first(),
+                    Some(method @ Value::String(_)) => Some(method),
+                    _ => None,
+                };
+                if obj.len() == 1
+                    && let Some(Value::String(method)) = method
+                    && LOCALIZED_METHODS.contains(&method.as_str())
+                {
+                    let mut result = Map::new();
+                    result.insert(
+                        "fake".to_string(),
+                        Value::Array(vec![
+                            Value::from(method.as_str()),
+                            Value::from(locale.code()),
+                        ]),
+                    );
+                    return Value::Object(result);
+                }
+                Value::Object(
+                    obj.iter()
+                        .map(|(key, value)| (key.clone(), Self::with_default_locale(value, locale)))
+                        .collect(),
+                )
+            }
+            Value::Array(arr) => Value::Array(
+                arr.iter()
+                    .map(|item| Self::with_default_locale(item, locale))
+                    .collect(),
+            ),
+            _ => value.clone(),
+        }
+    }
+
+    /// Generates a single record, as the first record of a dataset.
+    pub fn execute(&self) -> Result<Value> {
+        self.execute_at(0)
+    }
+
+    /// Generates record `index` of a dataset, which positions its per-record
+    /// sequences.
+    pub fn execute_at(&self, index: u64) -> Result<Value> {
+        datetime::with_now(self.now, || {
+            // Variables are in dependency order and each one sees those generated
+            // before it. The data is only referenced during `execute`, so `make_mut`
+            // inserts in place rather than copying the map.
+            let mut variables = Map::new();
+            variables.insert(RECORD_VARIABLE.to_string(), Value::from(index));
+            let mut data = Arc::new(Value::Object(variables));
+            for (name, node) in &self.variables {
+                // An omitted optional variable is simply not set
+                let Some(value) = node.execute_entry(&data)? else {
+                    continue;
+                };
+                if let Value::Object(variables) = Arc::make_mut(&mut data) {
+                    variables.insert(name.clone(), value);
+                }
+            }
+
+            self.schema.execute(&data)
+        })
+    }
+
+    /// Whether the config has `unique` fields, whose tracking depends on the order
+    /// records are generated in.
+    pub fn has_unique(&self) -> bool {
+        self.schema.has_unique() || self.variables.iter().any(|(_, node)| node.has_unique())
+    }
+
+    /// Forgets the values emitted so far by `unique` fields.
+    pub fn reset_unique(&self) {
+        self.schema.reset_unique();
+        self.variables
+            .iter()
+            .for_each(|(_, node)| node.reset_unique());
+    }
+
+    /// Lets `ref`s to `entity` pick from `keys`, the entity's primary keys.
+    pub(crate) fn bind_references(&self, entity: &str, keys: &Arc<Vec<Value>>) {
+        self.schema.bind_references(entity, keys);
+        self.variables
+            .iter()
+            .for_each(|(_, node)| node.bind_references(entity, keys));
+    }
+
+    /// Whether the config has named sequence counters, which advance in the
+    /// order records are generated in.
+    pub fn has_counters(&self) -> bool {
+        self.schema.has_counters() || self.variables.iter().any(|(_, node)| node.has_counters())
+    }
+
+    /// Restarts named sequence counters from their first value.
+    pub fn reset_counters(&self) {
+        self.schema.reset_counters();
+        self.variables
+            .iter()
+            .for_each(|(_, node)| node.reset_counters());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_constant_subtrees_are_folded() {
+        let node = PlanNode::compile(&json!({
+            "version": "1.0",
+            "code": {"cat": ["ABCD", "-", "XXXX"]},
+            "tags": ["a", "b"]
+        }))
+        .unwrap();
+
+        match node {
+            PlanNode::Literal(value) => {
+                assert_eq!(value["code"], "ABCD-XXXX");
+                assert_eq!(value["tags"], json!(["a", "b"]));
+            }
+            other => panic!("expected a literal, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_fake_and_logic_nodes() {
+        let node = PlanNode::compile(&json!({
+            "code": {"fake": ["regex", "[A-Z]{3}"]},
+            "id": {"fake": ["uuid"]},
+            "name": {"var": "name"}
+        }))
+        .unwrap();
+
+        let PlanNode::Object(fields) = &node else {
+            panic!("expected an object, got {node:?}");
+        };
+        assert!(matches!(fields[0].1, PlanNode::Regex(_)));
+        assert!(matches!(fields[1].1, PlanNode::Fake(_)));
+        assert!(matches!(fields[2].1, PlanNode::Logic(_)));
+
+        let data = Arc::new(json!({"name": "Ada"}));
+        let result = node.execute(&data).unwrap();
+        assert_eq!(result["code"].as_str().unwrap().len(), 3);
+        assert_eq!(result["id"].as_str().unwrap().len(), 36);
+        assert_eq!(result["name"], "Ada");
+    }
+
+    #[test]
+    fn test_repeat_with_fixed_count_and_index() {
+        let node = PlanNode::compile(&json!({
+            "repeat": [3, {
+                "position": {"var": "$index"},
+                "label": {"cat": [{"var": "prefix"}, {"var": "$index"}]}
+            }]
+        }))
+        .unwrap();
+
+        let data = Arc::new(json!({"prefix": "item-"}));
+        let result = node.execute(&data).unwrap();
+        assert_eq!(
+            result,
+            json!([
+                {"position": 0, "label": "item-0"},
+                {"position": 1, "label": "item-1"},
+                {"position": 2, "label": "item-2"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_repeat_with_range() {
+        let node = PlanNode::compile(&json!({"repeat": [[0, 4], {"fake": ["uuid"]}]})).unwrap();
+        let data = Arc::new(json!({}));
+
+        let mut lengths = std::collections::HashSet::new();
+        for _ in 0..100 {
+            let result = node.execute(&data).unwrap();
+            let items = result.as_array().unwrap();
+            assert!(items.len() <= 4);
+            assert!(items.iter().all(|id| id.as_str().unwrap().len() == 36));
+            lengths.insert(items.len());
+        }
+        assert!(lengths.len() > 1);
+    }
+
+    #[test]
+    fn test_invalid_repeat() {
+        assert!(PlanNode::compile(&json!({"repeat": [3]})).is_err());
+        assert!(PlanNode::compile(&json!({"repeat": [-1, "x"]})).is_err());
+        assert!(matches!(
+            PlanNode::compile(&json!({"repeat": [[5, 2], "x"]})),
+            Err(DataFakeError::InvalidRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_default_locale_applies_to_nested_calls() {
+        let schema = json!({
+            "name": {"fake": ["name"]},
+            "explicit": {"fake": ["name", "de_DE"]},
+            "label": {"cat": [{"fake": ["city"]}, "!"]},
+            "id": {"fake": ["uuid"]},
+            "short": {"fake": "city"}
+        });
+
+        let localized = ExecutionPlan::with_default_locale(&schema, Locale::JaJp);
+        assert_eq!(localized["name"], json!({"fake": ["name", "ja_JP"]}));
+        assert_eq!(localized["explicit"], json!({"fake": ["name", "de_DE"]}));
+        assert_eq!(
+            localized["label"],
+            json!({"cat": [{"fake": ["city", "ja_JP"]}, "!"]})
+        );
+        assert_eq!(localized["id"], json!({"fake": ["uuid"]}));
+        assert_eq!(localized["short"], json!({"fake": ["city", "ja_JP"]}));
+    }
+
+    #[test]
+    fn test_execute_plan_with_variables() {
+        let config: DataFakeConfig = serde_json::from_value(json!({
+            "variables": {"userId": {"fake": ["uuid"]}},
+            "schema": {
+                "id": {"var": "userId"},
+                "same": {"==": [{"var": "userId"}, {"var": "userId"}]}
+            }
+        }))
+        .unwrap();
+
+        let plan = ExecutionPlan::compile(&config).unwrap();
+        let first = plan.execute().unwrap();
+        let second = plan.execute().unwrap();
+
+        assert_eq!(first["same"], true);
+        assert_ne!(first["id"], second["id"]);
+    }
+
+    #[test]
+    fn test_unique_values() {
+        let node = PlanNode::compile(&json!({"unique": [{"fake": ["u8", 1, 5]}, 1000]})).unwrap();
+        let data = Arc::new(json!({}));
+
+        let mut values: Vec<u64> = (0..5)
+            .map(|_| node.execute(&data).unwrap().as_u64().unwrap())
+            .collect();
+        values.sort();
+        assert_eq!(values, [1, 2, 3, 4, 5]);
+
+        // The value space is exhausted
+        assert!(matches!(
+            node.execute(&data),
+            Err(DataFakeError::UniqueValuesExhausted { attempts: 1000, .. })
+        ));
+
+        node.reset_unique();
+        assert!(node.execute(&data).is_ok());
+        assert!(node.has_unique());
+
+        assert!(PlanNode::compile(&json!({"unique": [{"fake": ["u8"]}, 0]})).is_err());
+    }
+
+    #[test]
+    fn test_optional_and_nullable() {
+        let node = PlanNode::compile(&json!({
+            "id": 1,
+            "nickname": {"optional": [0.5, {"fake": ["username"]}]},
+            "phone": {"nullable": [0.5, {"fake": ["phone_number"]}]},
+            "never": {"optional": [1, "x"]},
+            "always": {"nullable": [0, "y"]},
+            "tags": [{"optional": [1, "a"]}, "b"]
+        }))
+        .unwrap();
+        let data = Arc::new(json!({}));
+
+        let records: Vec<Value> = (0..200).map(|_| node.execute(&data).unwrap()).collect();
+        let present = records
+            .iter()
+            .filter(|r| r.get("nickname").is_some())
+            .count();
+        let nulls = records.iter().filter(|r| r["phone"].is_null()).count();
+        assert!((50..150).contains(&present), "{present}");
+        assert!((50..150).contains(&nulls), "{nulls}");
+        for record in &records {
+            assert!(record.get("never").is_none());
+            assert!(record.as_object().unwrap().contains_key("phone"));
+            assert_eq!(record["always"], "y");
+            assert_eq!(record["tags"], json!(["b"]));
+        }
+
+        assert!(PlanNode::compile(&json!({"optional": [1.5, "x"]})).is_err());
+        assert!(PlanNode::compile(&json!({"nullable": "x"})).is_err());
+    }
+
+    #[test]
+    fn test_sequences() {
+        let config: DataFakeConfig = serde_json::from_value(json!({
+            "schema": {
+                "id": {"sequence": {}},
+                "invoice": {"sequence": {"start": 42, "pad": 6, "prefix": "INV-"}},
+                "countdown": {"sequence": {"start": 10, "step": -5}},
+                "lines": {"repeat": [2, {
+                    "line": {"sequence": {"per": "item", "start": 1}},
+                    "global": {"sequence": {"counter": "line", "start": 100}}
+                }]},
+                "audit": {"sequence": {"counter": "line", "start": 100, "prefix": "L", "suffix": "!"}}
+            }
+        }))
+        .unwrap();
+        let plan = ExecutionPlan::compile(&config).unwrap();
+        assert!(plan.has_counters());
+
+        let first = plan.execute_at(0).unwrap();
+        let record = plan.execute_at(3).unwrap();
+        assert_eq!(first["id"], 1);
+        assert_eq!(first["invoice"], "INV-000042");
+        assert_eq!(record["id"], 4);
+        assert_eq!(record["invoice"], "INV-000045");
+        assert_eq!(record["countdown"], -5);
+        assert_eq!(record["lines"][0]["line"], 1);
+        assert_eq!(record["lines"][1]["line"], 2);
+
+        // Both sites advance the shared counter: three draws per record, with the
+        // record's fields generated in key order
+        assert_eq!(first["audit"], "L100!");
+        assert_eq!(first["lines"][0]["global"], 101);
+        assert_eq!(first["lines"][1]["global"], 102);
+        assert_eq!(record["audit"], "L103!");
+        assert_eq!(record["lines"][1]["global"], 105);
+
+        plan.reset_counters();
+        assert_eq!(plan.execute_at(9).unwrap()["audit"], "L100!");
+
+        // Sites sharing a counter can't disagree on its values
+        for (start, step) in [(1, 1), (100, 2)] {
+            let config: DataFakeConfig = serde_json::from_value(json!({
+                "variables": {"first": {"sequence": {"counter": "line", "start": 100}}},
+                "schema": {"second": {"sequence": {"counter": "line", "start": start, "step": step}}}
+            }))
+            .unwrap();
+            assert!(matches!(
+                ExecutionPlan::compile(&config),
+                Err(DataFakeError::InvalidConfig(_))
+            ));
+        }
+
+        assert!(
+            PlanNode::compile(&json!({"sequence": {"per": "item"}}))
+                .unwrap()
+                .execute(&Arc::new(json!({})))
+                .is_err()
+        );
+        assert!(PlanNode::compile(&json!({"sequence": {"per": "page"}})).is_err());
+        assert!(PlanNode::compile(&json!({"sequence": {"per": "item", "counter": "a"}})).is_err());
+        assert!(PlanNode::compile(&json!({"sequence": {"pad": -1}})).is_err());
+        assert!(PlanNode::compile(&json!({"sequence": {"begin": 1}})).is_err());
+        assert!(PlanNode::compile(&json!({"sequence": 1})).is_err());
+    }
+
+    #[test]
+    fn test_one_of() {
+        let node = PlanNode::compile(&json!({"one_of": [
+            {"fake": ["u8", 1, 5]},
+            {"kind": "text", "value": {"fake": ["word"]}}
+        ]}))
+        .unwrap();
+        let data = Arc::new(json!({}));
+
+        let values: Vec<Value> = (0..100).map(|_| node.execute(&data).unwrap()).collect();
+        assert!(values.iter().any(Value::is_number));
+        assert!(values.iter().any(|v| v["kind"] == "text"));
+
+        assert!(PlanNode::compile(&json!({"one_of": []})).is_err());
+    }
+}
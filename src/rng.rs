@@ -0,0 +1,53 @@
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::cell::RefCell;
+
+// The fake operator is invoked by datalogic-rs through the `Operator` trait, which
+// has no way to carry our RNG. The generator therefore installs its seeded RNG in a
+// thread local for the duration of a generation and every random draw goes through it.
+thread_local! {
+    static ACTIVE_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with `slot` installed as the active RNG of the current thread.
+///
+/// The (advanced) RNG is moved back into `slot` afterwards so the next call continues
+/// the same sequence. An empty slot means "use the thread RNG".
+pub(crate) fn with_installed<T>(slot: &mut Option<StdRng>, f: impl FnOnce() -> T) -> T {
+    let previous = ACTIVE_RNG.with(|cell| cell.replace(slot.take()));
+    let result = f();
+    *slot = ACTIVE_RNG.with(|cell| cell.replace(previous));
+    result
+}
+
+/// Calls `f` with the active RNG, falling back to the thread RNG when no seeded
+/// generation is in progress.
+pub(crate) fn with_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    ACTIVE_RNG.with(|cell| match cell.borrow_mut().as_mut() {
+        Some(rng) => f(rng),
+        None => f(&mut rand::rng()),
+    })
+}
+
+/// The RNG for record `index` of a generator seeded with `seed`.
+///
+/// Each record gets an independent stream, so records can be generated on any
+/// thread and in any order and still match a sequential run.
+pub(crate) fn record_rng(seed: u64, index: u64) -> StdRng {
+    StdRng::seed_from_u64(splitmix64(seed ^ splitmix64(index)))
+}
+
+/// Seed for entity `name` of a dataset seeded with `seed`, so each entity draws
+/// from its own stream regardless of which other entities exist.
+pub(crate) fn entity_seed(seed: u64, name: &str) -> u64 {
+    name.bytes().fold(splitmix64(seed), |hash, byte| {
+        splitmix64(hash ^ byte as u64)
+    })
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
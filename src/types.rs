@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataFakeConfig {
@@ -13,6 +13,35 @@ pub struct DataFakeConfig {
     pub schema: Value,
 }
 
+/// A dataset of several entities (e.g. users, orders, order items), each with
+/// its own schema and record count. Fields link entities with `{"ref": "users"}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    pub entities: BTreeMap<String, EntityConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityConfig {
+    /// Number of records to generate
+    pub count: usize,
+
+    /// Top-level field whose values other entities reference
+    #[serde(default = "default_primary_key")]
+    pub primary_key: String,
+
+    #[serde(default)]
+    pub variables: HashMap<String, Value>,
+
+    pub schema: Value,
+}
+
+fn default_primary_key() -> String {
+    "id".to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Metadata {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -24,6 +53,23 @@ pub struct Metadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// Seed for the random number generator. The same config and seed always
+    /// produce the same output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+
+    /// Default locale for locale-aware fake methods that don't specify one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// Instant that `now`, `today` and relative datetime bounds are resolved
+    /// against, as an ISO 8601 date or datetime. Defaults to the time the
+    /// generator is created, or to
+    /// [`SEEDED_EPOCH`](crate::operators::datetime::SEEDED_EPOCH) when it is
+    /// seeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub now: Option<String>,
+
     #[serde(flatten)]
     pub extra: HashMap<String, Value>,
 }